@@ -0,0 +1,275 @@
+use crate::error::SudokuError;
+use crate::solver::{solve_async, SolveOptions};
+use crate::table::Table;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// What happened to a single puzzle of a batch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BatchStatus {
+    Solved,
+    Unsolvable,
+    MultipleSolutions,
+    /// The line could not be read or its givens break the rules.
+    Invalid(String),
+}
+
+impl BatchStatus {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            BatchStatus::Solved => "solved",
+            BatchStatus::Unsolvable => "unsolvable",
+            BatchStatus::MultipleSolutions => "multiple",
+            BatchStatus::Invalid(_) => "invalid",
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PuzzleReport<T = Table> {
+    pub status: BatchStatus,
+    /// First solution found, if any.
+    pub solution: Option<T>,
+    /// Solutions found. The search stops at the solve limit, but always
+    /// goes on to a second solution, so it may exceed a limit of one.
+    pub solution_count: usize,
+    pub nodes: usize,
+    /// Time spent searching, without parsing or waiting for a worker.
+    pub elapsed: Duration,
+}
+
+impl<T: std::fmt::Display> PuzzleReport<T> {
+    /// Tab separated `solution status count nodes microseconds`, with `-`
+    /// for a missing solution and the reason appended for invalid puzzles.
+    pub fn line(&self) -> String {
+        let solution = match &self.solution {
+            Some(solution) => solution.to_string(),
+            None => String::from("-"),
+        };
+        let mut line = format!(
+            "{}\t{}\t{}\t{}\t{}",
+            solution, self.status.name(), self.solution_count, self.nodes, self.elapsed.as_micros()
+        );
+        if let BatchStatus::Invalid(reason) = &self.status {
+            line.push('\t');
+            line.push_str(reason);
+        }
+        return line;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchOptions {
+    /// Puzzles solved at the same time, each by a single search worker.
+    pub workers: usize,
+    /// Used for every puzzle, except for `threads`. The search always goes
+    /// on to a second solution, enough to tell a unique puzzle apart, even
+    /// with a lower limit.
+    pub solve: SolveOptions,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        BatchOptions {
+            workers: 16,
+            solve: SolveOptions::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BatchSummary {
+    pub puzzles: usize,
+    pub solved: usize,
+    pub unsolvable: usize,
+    pub multiple: usize,
+    pub invalid: usize,
+}
+
+impl BatchSummary {
+    fn record(&mut self, status: &BatchStatus) {
+        self.puzzles += 1;
+        match status {
+            BatchStatus::Solved => self.solved += 1,
+            BatchStatus::Unsolvable => self.unsolvable += 1,
+            BatchStatus::MultipleSolutions => self.multiple += 1,
+            BatchStatus::Invalid(_) => self.invalid += 1,
+        }
+    }
+}
+
+/// Streams one line format puzzle per line from `input` and writes one
+/// [`PuzzleReport::line`] per puzzle to `output`, in input order. Blank
+/// lines and lines starting with `#` are skipped.
+///
+/// Rather than spreading one search tree over the [`crate::bus::table_bus::TableBus`],
+/// each puzzle gets a single worker and `workers` puzzles are solved at
+/// once. Only a bounded number of puzzles is read ahead of the first one
+/// not yet written, so arbitrarily large files run in constant memory.
+pub fn solve_batch<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: impl BufRead,
+    output: &mut impl Write,
+    options: BatchOptions,
+) -> io::Result<BatchSummary> {
+    let workers = options.workers.max(1);
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(workers)
+        .enable_all()
+        .build()?;
+    let mut solve_options = options.solve;
+    solve_options.threads = 1;
+    solve_options.limit = solve_options.limit.map(|limit| limit.max(2)).or(Option::Some(2));
+    return runtime.block_on(async move {
+        let capacity = workers * 16;
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let mut pending = BTreeMap::new();
+        let mut summary = BatchSummary::default();
+        let mut read = 0;
+        let mut written = 0;
+        for line in input.lines() {
+            let line = line?;
+            let puzzle = line.trim();
+            if puzzle.is_empty() || puzzle.starts_with('#') {
+                continue;
+            }
+            while read - written >= capacity {
+                let (index, report) = receiver.recv().await.expect("batch sender dropped");
+                pending.insert(index, report);
+                written = write_ready(&mut pending, written, output, &mut summary)?;
+            }
+            let sender = sender.clone();
+            let puzzle = String::from(puzzle);
+            let solve_options = solve_options.clone();
+            let index = read;
+            tokio::spawn(async move {
+                let report = solve_puzzle::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(&puzzle, solve_options).await;
+                // The receiver only goes away when writing failed.
+                let _ = sender.send((index, report));
+            });
+            read += 1;
+        }
+        drop(sender);
+        while written < read {
+            let (index, report) = receiver.recv().await.expect("batch worker panicked");
+            pending.insert(index, report);
+            written = write_ready(&mut pending, written, output, &mut summary)?;
+        }
+        output.flush()?;
+        return Result::Ok(summary);
+    });
+}
+
+/// Writes the reports following `written` without a gap, returns the new count.
+fn write_ready<T: std::fmt::Display>(
+    pending: &mut BTreeMap<usize, PuzzleReport<T>>,
+    written: usize,
+    output: &mut impl Write,
+    summary: &mut BatchSummary,
+) -> io::Result<usize> {
+    let mut written = written;
+    while let Some(report) = pending.remove(&written) {
+        writeln!(output, "{}", report.line())?;
+        summary.record(&report.status);
+        written += 1;
+    }
+    return Result::Ok(written);
+}
+
+async fn solve_puzzle<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    puzzle: &str,
+    options: SolveOptions,
+) -> PuzzleReport<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>> {
+    let mut report = PuzzleReport {
+        status: BatchStatus::Unsolvable,
+        solution: Option::None,
+        solution_count: 0,
+        nodes: 0,
+        elapsed: Duration::default(),
+    };
+    let table: Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> = match puzzle.parse() {
+        Ok(table) => table,
+        Err(error) => {
+            report.status = BatchStatus::Invalid(error.to_string());
+            return report;
+        }
+    };
+    let started = Instant::now();
+    match solve_async(&table, options).await {
+        Ok(outcome) => {
+            report.status = match outcome.solution_count {
+                1 => BatchStatus::Solved,
+                _ => BatchStatus::MultipleSolutions,
+            };
+            report.solution_count = outcome.solution_count;
+            report.nodes = outcome.stats.nodes;
+            report.solution = outcome.solutions.into_iter().next();
+        }
+        Err(SudokuError::SearchExhausted) => (),
+        Err(error) => report.status = BatchStatus::Invalid(error.to_string()),
+    }
+    report.elapsed = started.elapsed();
+    return report;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_reports_in_input_order() {
+        let input = "\
+            800000000003600000070090200050007000000045700000100030001000068008500010090000400\n\
+            \n\
+            # comments are skipped\n\
+            009218000170096800040050006451060370000005009902370500600501000000049257094800013\n\
+            110000000000000000000000000000000000000000000000000000000000000000000000000000000\n\
+            000000000000000000000000000000000000000000000000000000000000000000000000000000000\n\
+            12345\n";
+        let mut output = Vec::new();
+        let options = BatchOptions{ workers: 3, ..BatchOptions::default() };
+        let summary = solve_batch::<9, 3, 3>(input.as_bytes(), &mut output, options).unwrap();
+        assert_eq!(summary, BatchSummary { puzzles: 5, solved: 2, unsolvable: 0, multiple: 1, invalid: 2 });
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<Vec<&str>> = output.lines().map(|line| line.split('\t').collect()).collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0][0], "812753649943682175675491283154237896369845721287169534521974368438526917796318452");
+        assert_eq!(lines[1][0], "369218745175496832248753196451962378736185429982374561627531984813649257594827613");
+        let statuses: Vec<&str> = lines.iter().map(|line| line[1]).collect();
+        assert_eq!(statuses, vec!["solved", "solved", "invalid", "multiple", "invalid"]);
+        assert_eq!(lines[0][2], "1");
+        assert_eq!(lines[3][2], "2");
+        assert_eq!(lines[2][0], "-");
+        assert!(lines[4][5].contains("expected 81 cells"));
+    }
+
+    #[test]
+    fn test_count_agrees_with_status() {
+        let input = "000000000000000000000000000000000000000000000000000000000000000000000000000000000\n";
+        let report = |limit: usize| {
+            let mut output = Vec::new();
+            let solve = SolveOptions{ limit: Option::Some(limit), ..SolveOptions::default() };
+            let summary = solve_batch::<9, 3, 3>(input.as_bytes(), &mut output, BatchOptions{ workers: 1, solve: solve }).unwrap();
+            assert_eq!(summary.multiple, 1);
+            let output = String::from_utf8(output).unwrap();
+            return output.split('\t').nth(2).unwrap().to_string();
+        };
+        assert_eq!(report(1), "2");
+        assert_eq!(report(5), "5");
+    }
+
+    #[test]
+    fn test_reports_unsolvable_puzzles() {
+        let mut output = Vec::new();
+        let summary = solve_batch::<4, 2, 2>("12..\n....\n..3.\n..4.\n12.. .... ..3. ..4.\n".as_bytes(), &mut output, BatchOptions::default());
+        // Four cells per line are not a table; the last line is the unsolvable puzzle.
+        let summary = summary.unwrap();
+        assert_eq!(summary.invalid, 4);
+        assert_eq!(summary.unsolvable, 1);
+        let output = String::from_utf8(output).unwrap();
+        let last: Vec<&str> = output.lines().last().unwrap().split('\t').collect();
+        assert_eq!(&last[..3], &["-", "unsolvable", "0"]);
+    }
+}
@@ -1,57 +1,106 @@
-use std::future::Future;
 use crate::table::Table;
-use std::task::Context;
-use std::pin::Pin;
-use std::task::Poll;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::collections::VecDeque;
-use std::thread::sleep;
-use tokio::time::Duration;
+use tokio::sync::Notify;
 
-pub struct TableBus {
-    tables: Arc<Mutex<VecDeque<Table>>>,
+/// Shared work queue for the solver workers.
+///
+/// Every inserted table is outstanding until the worker that received it
+/// calls [`TableBus::done`], so `receive` only gives up once nothing is
+/// queued and nothing is being processed anymore.
+pub struct TableBus<T = Table> {
+    state: Arc<BusState<T>>,
 }
 
-impl TableBus {
-    pub fn new() -> TableBus {
-        TableBus { tables: Arc::new( Mutex::new( VecDeque::new() )) }
+struct BusState<T> {
+    tables: Mutex<VecDeque<T>>,
+    outstanding: AtomicUsize,
+    closed: AtomicBool,
+    notify: Notify,
+}
+
+impl<T> TableBus<T> {
+    pub fn new() -> TableBus<T> {
+        TableBus {
+            state: Arc::new(BusState {
+                tables: Mutex::new(VecDeque::new()),
+                outstanding: AtomicUsize::new(0),
+                closed: AtomicBool::new(false),
+                notify: Notify::new(),
+            })
+        }
     }
 
+    /// Number of queued tables, not counting the ones being processed.
     pub fn count(&self) -> usize {
-        self.tables.lock().unwrap().len()
+        self.state.tables.lock().unwrap().len()
+    }
+
+    /// Number of queued tables plus the ones received but not marked as done.
+    pub fn outstanding(&self) -> usize {
+        self.state.outstanding.load(Ordering::SeqCst)
     }
 
-    pub fn insert(&self, table: Table) {
-        self.tables.lock().unwrap().push_front(table)
+    pub fn insert(&self, table: T) {
+        self.state.outstanding.fetch_add(1, Ordering::SeqCst);
+        self.state.tables.lock().unwrap().push_front(table);
+        self.state.notify.notify_waiters();
     }
 
-    pub async fn receive(&self) -> Option<Table> {
-        for _ in 0..10 {
-            let t = self.tables.lock().unwrap().pop_front();
-            if t.is_some() {
-                return t
+    /// Waits for the next table. Returns `None` once the bus is closed or
+    /// when no table is queued or in flight.
+    pub async fn receive(&self) -> Option<T> {
+        loop {
+            let notified = self.state.notify.notified();
+            if self.is_closed() {
+                return Option::None;
             }
-            sleep(Duration::from_millis(50))
+            if let Some(table) = self.state.tables.lock().unwrap().pop_front() {
+                return Option::Some(table);
+            }
+            if self.outstanding() == 0 {
+                return Option::None;
+            }
+            notified.await;
         }
-        return  Option::None
     }
-}
 
-impl Clone for TableBus {
-    fn clone(&self) -> Self {
-        TableBus { tables: self.tables.clone() }
+    /// Marks a received table as processed.
+    pub fn done(&self) {
+        if self.state.outstanding.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.notify.notify_waiters();
+        }
+    }
+
+    /// Takes every queued table at once, marking them as processed.
+    pub fn drain(&self) -> Vec<T> {
+        let tables: Vec<T> = self.state.tables.lock().unwrap().drain(..).collect();
+        self.state.outstanding.fetch_sub(tables.len(), Ordering::SeqCst);
+        return tables;
+    }
+
+    /// Drops all queued tables and wakes every waiting receiver.
+    pub fn close(&self) {
+        self.state.closed.store(true, Ordering::SeqCst);
+        self.drain();
+        self.state.notify.notify_waiters();
     }
 
-    fn clone_from(&mut self, source: &Self) {
-        TableBus { tables: source.tables.clone() };
+    pub fn is_closed(&self) -> bool {
+        self.state.closed.load(Ordering::SeqCst)
     }
 }
 
-impl Future for Table {
-    type Output = Table;
+impl<T> Clone for TableBus<T> {
+    fn clone(&self) -> Self {
+        TableBus { state: self.state.clone() }
+    }
+}
 
-    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
-        Poll::Ready(self.clone())
+impl<T> Default for TableBus<T> {
+    fn default() -> Self {
+        TableBus::new()
     }
 }
 
@@ -62,6 +111,7 @@ mod test {
     use crate::table::Table;
     use crate::Settable;
     use crate::point::Point;
+    use tokio::time::{sleep, Duration};
 
     #[tokio::test]
     async fn test_polling() {
@@ -72,7 +122,54 @@ mod test {
         let t2 = *t1.set_in_point(&Point{ x: 3, y: 6 }, 1).unwrap();
         tb.insert(t1);
         tb.insert(t2);
-        println!("{:?}", tb.receive().await.unwrap().await);
-        println!("{:?}", tb.receive().await.unwrap().await);
+        println!("{:?}", tb.receive().await.unwrap());
+        println!("{:?}", tb.receive().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_receive_ends_when_work_is_done() {
+        let tb = TableBus::new();
+        tb.insert(Table::new());
+        assert!(tb.receive().await.is_some());
+        assert_eq!(tb.outstanding(), 1);
+        tb.done();
+        assert_eq!(tb.outstanding(), 0);
+        assert!(tb.receive().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_receive_waits_for_work_in_flight() {
+        let tb = TableBus::new();
+        tb.insert(Table::new());
+        let table = tb.receive().await.unwrap();
+
+        let waiting = tb.clone();
+        let handle = tokio::spawn(async move {
+            let received = waiting.receive().await;
+            if received.is_some() {
+                waiting.done();
+            }
+            return received.is_some();
+        });
+        sleep(Duration::from_millis(20)).await;
+        tb.insert(*table.set_in_point(&Point{ x: 0, y: 0 }, 1).unwrap());
+        tb.done();
+
+        assert!(handle.await.unwrap());
+        assert_eq!(tb.outstanding(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_close_wakes_receivers() {
+        let tb = TableBus::<Table>::new();
+        tb.insert(Table::new());
+        let _in_flight = tb.receive().await.unwrap();
+
+        let waiting = tb.clone();
+        let handle = tokio::spawn(async move { waiting.receive().await.is_none() });
+        sleep(Duration::from_millis(20)).await;
+        tb.close();
+
+        assert!(handle.await.unwrap());
     }
 }
@@ -0,0 +1,231 @@
+use crate::point::Point;
+
+/// Set of values, bit `v` stands for value `v`. Bit 0 is never used.
+pub type CandidateMask = u32;
+
+pub fn mask_of(value: u8) -> CandidateMask {
+    return 1 << value;
+}
+
+/// Mask holding every value of a table with `size` values.
+pub fn full_mask(size: usize) -> CandidateMask {
+    return ((1u64 << (size + 1)) - 2) as CandidateMask;
+}
+
+/// Values held by `mask` in increasing order.
+pub fn mask_values(mask: CandidateMask) -> Vec<u8> {
+    let mut values = Vec::with_capacity(mask.count_ones() as usize);
+    let mut rest = mask;
+    while rest != 0 {
+        let value = rest.trailing_zeros() as u8;
+        values.push(value);
+        rest &= rest - 1;
+    }
+    return values;
+}
+
+/// Possible values of every point of a table together with the values
+/// already placed in each row, column and zone.
+///
+/// Kept up to date by the owning table on every placement, so reading the
+/// candidates of a point never has to look at its peers.
+#[derive(Clone, PartialEq)]
+pub struct CandidateGrid<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> {
+    cells: [[CandidateMask; SIZE]; SIZE],
+    rows: [CandidateMask; SIZE],
+    columns: [CandidateMask; SIZE],
+    zones: [CandidateMask; SIZE],
+}
+
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    /// Builds the grid for `fields[y][x]`, where `0` marks an empty point.
+    /// Values above `SIZE` are left for the validators to reject.
+    pub fn from_fields(fields: &[[u8; SIZE]; SIZE]) -> Self {
+        let mut grid = CandidateGrid {
+            cells: [[0; SIZE]; SIZE],
+            rows: [0; SIZE],
+            columns: [0; SIZE],
+            zones: [0; SIZE],
+        };
+        for (y, row) in fields.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                let bit = Self::placed_bit(*value);
+                grid.rows[y] |= bit;
+                grid.columns[x] |= bit;
+                grid.zones[Self::zone_index(&Point{x: x, y: y})] |= bit;
+            }
+        }
+        let full = full_mask(SIZE);
+        for (y, row) in fields.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                if *value == 0 {
+                    let point = Point{x: x, y: y};
+                    grid.cells[y][x] = full & !grid.placed_around(&point);
+                }
+            }
+        }
+        return grid;
+    }
+
+    /// Builds a grid from its raw masks without checking that they agree.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_masks(
+        cells: [[CandidateMask; SIZE]; SIZE],
+        rows: [CandidateMask; SIZE],
+        columns: [CandidateMask; SIZE],
+        zones: [CandidateMask; SIZE],
+    ) -> Self {
+        return CandidateGrid { cells: cells, rows: rows, columns: columns, zones: zones };
+    }
+
+    /// Bit of a placed `value`, none for empty points and values above `SIZE`.
+    fn placed_bit(value: u8) -> CandidateMask {
+        return if value != 0 && value as usize <= SIZE { mask_of(value) } else { 0 };
+    }
+
+    /// Zones are numbered left to right, top to bottom.
+    pub fn zone_index(point: &Point) -> usize {
+        return (point.y / ZONE_HEIGHT) * (SIZE / ZONE_WIDTH) + point.x / ZONE_WIDTH;
+    }
+
+    /// Possible values of `point`; empty for points holding a value.
+    pub fn candidates(&self, point: &Point) -> CandidateMask {
+        return self.cells[point.y][point.x];
+    }
+
+    pub fn row_mask(&self, y: usize) -> CandidateMask {
+        return self.rows[y];
+    }
+
+    pub fn column_mask(&self, x: usize) -> CandidateMask {
+        return self.columns[x];
+    }
+
+    pub fn zone_mask(&self, zone: usize) -> CandidateMask {
+        return self.zones[zone];
+    }
+
+    /// Values placed in the row, column or zone of `point`.
+    pub fn placed_around(&self, point: &Point) -> CandidateMask {
+        return self.rows[point.y] | self.columns[point.x] | self.zones[Self::zone_index(point)];
+    }
+
+    /// Records `value` in the empty `point` and removes it from its peers.
+    pub fn place(&mut self, point: &Point, value: u8) {
+        let bit = mask_of(value);
+        let zone = Self::zone_index(point);
+        self.cells[point.y][point.x] = 0;
+        self.rows[point.y] |= bit;
+        self.columns[point.x] |= bit;
+        self.zones[zone] |= bit;
+        for i in 0..SIZE {
+            self.cells[point.y][i] &= !bit;
+            self.cells[i][point.x] &= !bit;
+        }
+        let x_offset = (point.x / ZONE_WIDTH) * ZONE_WIDTH;
+        let y_offset = (point.y / ZONE_HEIGHT) * ZONE_HEIGHT;
+        for y in y_offset..y_offset + ZONE_HEIGHT {
+            for x in x_offset..x_offset + ZONE_WIDTH {
+                self.cells[y][x] &= !bit;
+            }
+        }
+    }
+
+    /// Takes `value` back out of `point`, which `fields` already holds as
+    /// empty. Only the row, column and zone of `point` are rebuilt: the point
+    /// and its empty peers get `value` back unless another placement rules
+    /// it out, and every other elimination is kept.
+    pub fn remove(&mut self, fields: &[[u8; SIZE]; SIZE], point: &Point, value: u8) {
+        let zone = Self::zone_index(point);
+        let x_offset = (point.x / ZONE_WIDTH) * ZONE_WIDTH;
+        let y_offset = (point.y / ZONE_HEIGHT) * ZONE_HEIGHT;
+        self.rows[point.y] = 0;
+        self.columns[point.x] = 0;
+        self.zones[zone] = 0;
+        for i in 0..SIZE {
+            self.rows[point.y] |= Self::placed_bit(fields[point.y][i]);
+            self.columns[point.x] |= Self::placed_bit(fields[i][point.x]);
+            self.zones[zone] |= Self::placed_bit(fields[y_offset + i / ZONE_WIDTH][x_offset + i % ZONE_WIDTH]);
+        }
+        self.cells[point.y][point.x] = full_mask(SIZE) & !self.placed_around(point);
+
+        let bit = Self::placed_bit(value);
+        let mut peers = Vec::with_capacity(3 * SIZE);
+        for i in 0..SIZE {
+            peers.push(Point{x: i, y: point.y});
+            peers.push(Point{x: point.x, y: i});
+            peers.push(Point{x: x_offset + i % ZONE_WIDTH, y: y_offset + i / ZONE_WIDTH});
+        }
+        for peer in peers {
+            if fields[peer.y][peer.x] == 0 && self.placed_around(&peer) & bit == 0 {
+                self.cells[peer.y][peer.x] |= bit;
+            }
+        }
+    }
+
+    /// Removes `value` from the candidates of `point`.
+    pub fn eliminate(&mut self, point: &Point, value: u8) {
+        self.cells[point.y][point.x] &= !mask_of(value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_masks() {
+        assert_eq!(full_mask(4), 0b11110);
+        assert_eq!(full_mask(25).count_ones(), 25);
+        assert_eq!(mask_values(0b10100110), vec![1, 2, 5, 7]);
+        assert_eq!(mask_values(mask_of(9)), vec![9]);
+    }
+
+    #[test]
+    fn test_place_updates_peers() {
+        let mut grid = CandidateGrid::<4, 2, 2>::from_fields(&[[0; 4]; 4]);
+        grid.place(&Point{x: 1, y: 0}, 3);
+        assert_eq!(grid.candidates(&Point{x: 1, y: 0}), 0);
+        assert_eq!(mask_values(grid.candidates(&Point{x: 3, y: 0})), vec![1, 2, 4]);
+        assert_eq!(mask_values(grid.candidates(&Point{x: 1, y: 3})), vec![1, 2, 4]);
+        assert_eq!(mask_values(grid.candidates(&Point{x: 0, y: 1})), vec![1, 2, 4]);
+        assert_eq!(mask_values(grid.candidates(&Point{x: 2, y: 2})), vec![1, 2, 3, 4]);
+        assert_eq!(grid.row_mask(0), mask_of(3));
+        assert_eq!(grid.column_mask(1), mask_of(3));
+        assert_eq!(grid.zone_mask(0), mask_of(3));
+        assert_eq!(grid.zone_mask(1), 0);
+    }
+
+    #[test]
+    fn test_incremental_matches_rebuild() {
+        let mut fields = [[0u8; 6]; 6];
+        let mut grid = CandidateGrid::<6, 2, 3>::from_fields(&fields);
+        for (x, y, value) in [(0, 0, 1), (4, 1, 2), (2, 3, 6), (5, 5, 1), (3, 0, 5)].iter() {
+            fields[*y][*x] = *value;
+            grid.place(&Point{x: *x, y: *y}, *value);
+        }
+        assert!(grid == CandidateGrid::<6, 2, 3>::from_fields(&fields));
+    }
+
+    #[test]
+    fn test_remove_keeps_eliminations() {
+        let mut fields = [[0u8; 4]; 4];
+        fields[0][1] = 3;
+        fields[3][2] = 3;
+        let mut grid = CandidateGrid::<4, 2, 2>::from_fields(&fields);
+        grid.eliminate(&Point{x: 3, y: 0}, 1);
+        fields[0][1] = 0;
+        grid.remove(&fields, &Point{x: 1, y: 0}, 3);
+        assert_eq!(grid.row_mask(0), 0);
+        assert_eq!(grid.zone_mask(0), 0);
+        assert_eq!(mask_values(grid.candidates(&Point{x: 1, y: 0})), vec![1, 2, 3, 4]);
+        assert_eq!(mask_values(grid.candidates(&Point{x: 3, y: 0})), vec![2, 3, 4]);
+        // Column 3 still holds 3 further down.
+        assert_eq!(mask_values(grid.candidates(&Point{x: 2, y: 0})), vec![1, 2, 4]);
+        assert!(grid == {
+            let mut rebuilt = CandidateGrid::<4, 2, 2>::from_fields(&fields);
+            rebuilt.eliminate(&Point{x: 3, y: 0}, 1);
+            rebuilt
+        });
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt;
+
+pub const USAGE: &str = "\
+Usage: sudoku <command> [options] [FILE...]
+
+Commands:
+  solve      print the solutions of each puzzle
+  count      print the number of solutions of each puzzle
+  validate   check the givens and that the solution is unique
+  grade      rate the difficulty of each puzzle
+  explain    solve each puzzle step by step with human techniques, or
+             tell where it requires guessing
+  generate   print new puzzles with a unique solution
+  booklet    write a printable HTML booklet of the puzzles, with solutions;
+             a FILE may hold one puzzle per line
+  batch      solve a file with one puzzle per line, several at once, and
+             write `solution status count nodes microseconds` per puzzle
+
+Puzzles are read from each FILE, or from stdin when none is given or
+FILE is `-`. `.sdk` (SadMan) and `.ss` (Simple Sudoku) files are read
+in those formats, anything else as one line (`.` or `0` for blanks), as a framed grid,
+as a pencil mark grid listing the possible values of every point or as a
+puzzle code.
+
+Options:
+  -t, --threads N     workers used by the search, or puzzles solved at
+                      once by batch
+  -l, --limit N       stop after N solutions
+  -s, --strategy S    propagation (default), reduction, simple or dlx
+  -f, --format F      output as line (default), grid, marks, sdk, ss, svg
+                      or code
+  -o, --output FILE   write results to FILE instead of stdout; a .sdk, .ss
+                      or .svg extension picks that format
+      --size N        table size: 4, 6, 9 (default), 12, 16 or 25
+  -n, --count N       puzzles to generate (default 1)
+      --seed N        seed for generate
+      --title T       booklet title
+      --per-page N    puzzles per booklet page (default 4)
+  -h, --help          print this message
+
+Exit codes: 0 solved, 1 unsolvable, 2 multiple solutions, 3 invalid input,
+4 explain needs a guess.";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    Solve,
+    Count,
+    Validate,
+    Grade,
+    Explain,
+    Generate,
+    Batch,
+    Booklet,
+    Help,
+}
+
+/// Search setups selectable from the command line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// Minimum remaining values with naked and hidden singles.
+    Propagation,
+    /// Propagation plus the eliminating human techniques on every branch.
+    Reduction,
+    /// Directional search trying every candidate, as the solver first did.
+    Simple,
+    /// Exact cover with dancing links.
+    DancingLinks,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Line,
+    Grid,
+    /// Pencil marks, the possible values of every point.
+    Marks,
+    Sdk,
+    SimpleSudoku,
+    /// Drawing with givens and solved values told apart.
+    Svg,
+    /// Compact URL safe code holding the givens and any solved values.
+    Code,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Args {
+    pub command: Command,
+    pub inputs: Vec<String>,
+    pub threads: Option<usize>,
+    pub limit: Option<usize>,
+    pub strategy: Strategy,
+    /// Explicit `--format`; otherwise it follows the output file.
+    pub format: Option<OutputFormat>,
+    pub output: Option<String>,
+    pub size: usize,
+    pub count: usize,
+    pub seed: Option<u64>,
+    pub title: Option<String>,
+    pub per_page: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArgsError(pub String);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.0);
+    }
+}
+
+fn number<T: std::str::FromStr>(option: &str, value: &str) -> Result<T, ArgsError> {
+    return value.parse().map_err(|_| ArgsError(format!("{} expects a number, got '{}'", option, value)));
+}
+
+impl Args {
+    pub fn parse(arguments: &[String]) -> Result<Args, ArgsError> {
+        let mut args = Args {
+            command: Command::Help,
+            inputs: Vec::new(),
+            threads: Option::None,
+            limit: Option::None,
+            strategy: Strategy::Propagation,
+            format: Option::None,
+            output: Option::None,
+            size: 9,
+            count: 1,
+            seed: Option::None,
+            title: Option::None,
+            per_page: Option::None,
+        };
+        let mut command = Option::None;
+        let mut rest = arguments.iter();
+        while let Some(argument) = rest.next() {
+            if argument == "-h" || argument == "--help" {
+                args.command = Command::Help;
+                return Result::Ok(args);
+            }
+            if !argument.starts_with('-') || argument == "-" {
+                match command {
+                    None => command = Option::Some(Args::command(argument)?),
+                    Some(_) => args.inputs.push(argument.clone()),
+                }
+                continue;
+            }
+            let (option, inline) = match argument.split_once('=') {
+                Some((option, value)) => (option, Option::Some(value.to_string())),
+                None => (argument.as_str(), Option::None),
+            };
+            let value = match inline.or_else(|| rest.next().cloned()) {
+                Some(value) => value,
+                None => return Result::Err(ArgsError(format!("{} expects a value", option))),
+            };
+            match option {
+                "-t" | "--threads" => args.threads = Option::Some(number(option, &value)?),
+                "-l" | "--limit" => args.limit = Option::Some(number(option, &value)?),
+                "-n" | "--count" => args.count = number(option, &value)?,
+                "-o" | "--output" => args.output = Option::Some(value),
+                "--size" => args.size = number(option, &value)?,
+                "--title" => args.title = Option::Some(value),
+                "--per-page" => args.per_page = Option::Some(number(option, &value)?),
+                "--seed" => args.seed = Option::Some(number(option, &value)?),
+                "-s" | "--strategy" => args.strategy = match value.as_str() {
+                    "propagation" => Strategy::Propagation,
+                    "reduction" => Strategy::Reduction,
+                    "simple" => Strategy::Simple,
+                    "dlx" => Strategy::DancingLinks,
+                    _ => return Result::Err(ArgsError(format!("unknown strategy '{}'", value))),
+                },
+                "-f" | "--format" => args.format = Option::Some(match value.as_str() {
+                    "line" => OutputFormat::Line,
+                    "grid" => OutputFormat::Grid,
+                    "marks" => OutputFormat::Marks,
+                    "sdk" => OutputFormat::Sdk,
+                    "ss" => OutputFormat::SimpleSudoku,
+                    "svg" => OutputFormat::Svg,
+                    "code" => OutputFormat::Code,
+                    _ => return Result::Err(ArgsError(format!("unknown format '{}'", value))),
+                }),
+                _ => return Result::Err(ArgsError(format!("unknown option '{}'", option))),
+            }
+        }
+        args.command = match command {
+            Some(command) => command,
+            None => return Result::Err(ArgsError(String::from("missing command"))),
+        };
+        return Result::Ok(args);
+    }
+
+    fn command(name: &str) -> Result<Command, ArgsError> {
+        return match name {
+            "solve" => Result::Ok(Command::Solve),
+            "count" => Result::Ok(Command::Count),
+            "validate" => Result::Ok(Command::Validate),
+            "grade" => Result::Ok(Command::Grade),
+            "explain" => Result::Ok(Command::Explain),
+            "generate" => Result::Ok(Command::Generate),
+            "batch" => Result::Ok(Command::Batch),
+            "booklet" => Result::Ok(Command::Booklet),
+            "help" => Result::Ok(Command::Help),
+            _ => Result::Err(ArgsError(format!("unknown command '{}'", name))),
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Args, ArgsError> {
+        let arguments: Vec<String> = line.split_whitespace().map(String::from).collect();
+        return Args::parse(&arguments);
+    }
+
+    #[test]
+    fn test_parses_command_options_and_inputs() {
+        let args = parse("solve -t 4 --limit=3 -s dlx puzzle.txt -f grid -").unwrap();
+        assert_eq!(args.command, Command::Solve);
+        assert_eq!(args.threads, Option::Some(4));
+        assert_eq!(args.limit, Option::Some(3));
+        assert_eq!(args.strategy, Strategy::DancingLinks);
+        assert_eq!(args.format, Option::Some(OutputFormat::Grid));
+        assert_eq!(args.inputs, vec![String::from("puzzle.txt"), String::from("-")]);
+
+        let args = parse("generate --count 5 --seed 9 --size 6").unwrap();
+        assert_eq!(args.command, Command::Generate);
+        assert_eq!((args.count, args.seed, args.size), (5, Option::Some(9), 6));
+        assert_eq!(parse("count --help").unwrap().command, Command::Help);
+
+        let args = parse("batch corpus.txt -o results.tsv").unwrap();
+        assert_eq!(args.command, Command::Batch);
+        assert_eq!(args.output, Option::Some(String::from("results.tsv")));
+
+        let args = parse("solve -s reduction puzzle.txt").unwrap();
+        assert_eq!(args.strategy, Strategy::Reduction);
+    }
+
+    #[test]
+    fn test_rejects_bad_arguments() {
+        assert!(parse("").is_err());
+        assert!(parse("unknown").is_err());
+        assert!(parse("solve --threads many").is_err());
+        assert!(parse("solve --limit").is_err());
+        assert!(parse("solve --strategy guess").is_err());
+        assert!(parse("solve --verbose 1").is_err());
+    }
+}
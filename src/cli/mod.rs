@@ -0,0 +1,660 @@
+pub mod args;
+
+use args::{Args, Command, OutputFormat, Strategy};
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use sudoku::batch::{solve_batch, BatchOptions};
+use sudoku::explainer::{explain, ExplainOutcome};
+use sudoku::fillers::FillStrategy;
+use sudoku::formats::code;
+use sudoku::formats::grid::parse_grid;
+use sudoku::formats::pencil_marks::{parse_pencil_marks, write_pencil_marks};
+use sudoku::formats::sadman::{read_sdk, write_sdk};
+use sudoku::formats::simple_sudoku::{read_ss, write_ss};
+use sudoku::formats::{PuzzleFile, PuzzleMetadata};
+use sudoku::generator::{Generator, Rng};
+use sudoku::grader::grade;
+use sudoku::render::html::{render_booklet, BookletOptions};
+use sudoku::render::svg::{render_svg, SvgOptions};
+use sudoku::techniques::Technique;
+use sudoku::point_selection::SelectionStrategy;
+use sudoku::point_selection::empty_point_search::SearchDirection;
+use sudoku::{count_solutions_with, solve, Backend, SolveOptions, SudokuError, Table};
+
+/// Process exit codes. When several puzzles are processed the most severe
+/// status wins; needing a guess is the mildest failure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved = 0,
+    Unsolvable = 1,
+    MultipleSolutions = 2,
+    InvalidInput = 3,
+    /// `explain` got stuck before the puzzle was solved.
+    RequiresGuessing = 4,
+}
+
+impl Ord for Status {
+    fn cmp(&self, other: &Status) -> Ordering {
+        return self.severity().cmp(&other.severity());
+    }
+}
+
+impl PartialOrd for Status {
+    fn partial_cmp(&self, other: &Status) -> Option<Ordering> {
+        return Option::Some(self.cmp(other));
+    }
+}
+
+impl Status {
+    fn severity(&self) -> u8 {
+        return match self {
+            Status::Solved => 0,
+            Status::RequiresGuessing => 1,
+            Status::Unsolvable => 2,
+            Status::MultipleSolutions => 3,
+            Status::InvalidInput => 4,
+        };
+    }
+
+    fn of_error(error: &SudokuError) -> Status {
+        return match error {
+            SudokuError::SearchExhausted => Status::Unsolvable,
+            _ => Status::InvalidInput,
+        };
+    }
+
+    fn of_count(count: usize) -> Status {
+        return match count {
+            0 => Status::Unsolvable,
+            1 => Status::Solved,
+            _ => Status::MultipleSolutions,
+        };
+    }
+}
+
+/// Failures of a single command; only the rules or the search decide
+/// between unsolvable and invalid, writing output counts as invalid input.
+#[derive(Debug)]
+pub enum CommandError {
+    Sudoku(SudokuError),
+    Io(io::Error),
+}
+
+impl CommandError {
+    fn status(&self) -> Status {
+        return match self {
+            CommandError::Sudoku(error) => Status::of_error(error),
+            CommandError::Io(_) => Status::InvalidInput,
+        };
+    }
+}
+
+impl From<SudokuError> for CommandError {
+    fn from(error: SudokuError) -> Self {
+        return CommandError::Sudoku(error);
+    }
+}
+
+impl From<io::Error> for CommandError {
+    fn from(error: io::Error) -> Self {
+        return CommandError::Io(error);
+    }
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            CommandError::Sudoku(error) => write!(f, "{}", error),
+            CommandError::Io(error) => write!(f, "{}", error),
+        };
+    }
+}
+
+pub fn run(arguments: &[String]) -> Status {
+    let args = match Args::parse(arguments) {
+        Ok(args) => args,
+        Err(error) => {
+            eprintln!("sudoku: {}\n\n{}", error, args::USAGE);
+            return Status::InvalidInput;
+        }
+    };
+    return match args.size {
+        4 => run_sized::<4, 2, 2>(&args),
+        6 => run_sized::<6, 2, 3>(&args),
+        9 => run_sized::<9, 3, 3>(&args),
+        12 => run_sized::<12, 3, 4>(&args),
+        16 => run_sized::<16, 4, 4>(&args),
+        25 => run_sized::<25, 5, 5>(&args),
+        size => {
+            eprintln!("sudoku: unsupported size {}", size);
+            Status::InvalidInput
+        }
+    };
+}
+
+fn run_sized<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(args: &Args) -> Status {
+    if args.command == Command::Help {
+        println!("{}", args::USAGE);
+        return Status::Solved;
+    }
+    if args.command == Command::Batch {
+        return batch::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(args);
+    }
+    let mut out = match open_output(args) {
+        Ok(out) => out,
+        Err(error) => {
+            eprintln!("{}: {}", args.output.as_deref().unwrap_or("-"), error);
+            return Status::InvalidInput;
+        }
+    };
+    let status = if args.command == Command::Generate {
+        generate::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(args, &mut out)
+    } else if args.command == Command::Booklet {
+        booklet::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(args, &mut out)
+    } else {
+        run_inputs::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(args, &mut out)
+    };
+    if let Err(error) = out.flush() {
+        eprintln!("sudoku: {}", error);
+        return Status::InvalidInput;
+    }
+    return status;
+}
+
+fn run_inputs<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    args: &Args,
+    out: &mut dyn Write,
+) -> Status {
+    let inputs = if args.inputs.is_empty() { vec![String::from("-")] } else { args.inputs.clone() };
+    let mut status = Status::Solved;
+    for input in inputs.iter() {
+        let file = match read_input::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(input) {
+            Ok(file) => file,
+            Err(message) => {
+                eprintln!("{}: {}", input, message);
+                status = status.max(Status::InvalidInput);
+                continue;
+            }
+        };
+        let result = match args.command {
+            Command::Solve => solve_command(args, out, &file),
+            Command::Count => count_command(args, out, &file.table),
+            Command::Validate => validate_command(args, out, &file.table),
+            Command::Explain => explain_command(out, &file.table),
+            _ => grade_command(out, &file.table),
+        };
+        let puzzle_status = match result {
+            Ok(puzzle_status) => puzzle_status,
+            Err(error) => {
+                eprintln!("{}: {}", input, error);
+                error.status()
+            }
+        };
+        match puzzle_status {
+            Status::Unsolvable => eprintln!("{}: no solution", input),
+            Status::MultipleSolutions => eprintln!("{}: multiple solutions", input),
+            _ => (),
+        }
+        status = status.max(puzzle_status);
+    }
+    return status;
+}
+
+fn extension(path: &str) -> Option<String> {
+    return Path::new(path).extension().map(|extension| extension.to_string_lossy().to_lowercase());
+}
+
+fn read_text(input: &str) -> Result<String, String> {
+    if input == "-" {
+        let mut text = String::new();
+        std::io::stdin().read_to_string(&mut text).map_err(|e| e.to_string())?;
+        return Result::Ok(text);
+    }
+    return std::fs::read_to_string(input).map_err(|e| e.to_string());
+}
+
+/// Reads one puzzle from a file, or stdin for `-`.
+///
+/// `.sdk` and `.ss` files are read in those formats. Anything else is read
+/// as a framed grid when it has vertical bars, falling back to pencil
+/// marks, as pencil marks when it has an entry per point and in the one
+/// line format otherwise.
+fn read_input<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: &str,
+) -> Result<PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, String> {
+    return parse_input(input, &read_text(input)?);
+}
+
+fn parse_input<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: &str,
+    text: &str,
+) -> Result<PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, String> {
+    let parsed = match extension(input).as_deref() {
+        Some("sdk") => read_sdk(text),
+        Some("ss") => read_ss(text),
+        _ => {
+            let table = if text.contains('|') || text.contains('│') {
+                parse_grid(text).or_else(|error| parse_pencil_marks(text).map_err(|_| error))
+            } else if text.split_whitespace().count() == SIZE * SIZE {
+                parse_pencil_marks(text)
+            } else if looks_like_code(text, SIZE * SIZE) {
+                code::decode(text).map(|shared| shared.progress.unwrap_or(shared.puzzle))
+            } else {
+                text.parse()
+            };
+            table.map(|table| PuzzleFile { table: table, metadata: PuzzleMetadata::default() })
+        }
+    };
+    return parsed.map_err(|e| e.to_string());
+}
+
+/// A single word of URL safe base64 that is not a whole one line table.
+fn looks_like_code(text: &str, cells: usize) -> bool {
+    let text = text.trim();
+    return text.len() != cells
+        && text.chars().any(|c| !c.is_ascii_digit())
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+}
+
+/// Where results go: the `--output` file or stdout.
+fn open_output(args: &Args) -> io::Result<Box<dyn Write>> {
+    return match &args.output {
+        None => Result::Ok(Box::new(BufWriter::new(io::stdout()))),
+        Some(path) => Result::Ok(Box::new(BufWriter::new(std::fs::File::create(path)?))),
+    };
+}
+
+/// The `--format` given, or the one matching the extension of `--output`.
+fn output_format(args: &Args) -> OutputFormat {
+    if let Some(format) = args.format {
+        return format;
+    }
+    return match args.output.as_deref().and_then(extension).as_deref() {
+        Some("sdk") => OutputFormat::Sdk,
+        Some("ss") => OutputFormat::SimpleSudoku,
+        Some("svg") => OutputFormat::Svg,
+        _ => OutputFormat::Line,
+    };
+}
+
+fn solve_options(args: &Args, default_limit: Option<usize>) -> SolveOptions {
+    let mut options = SolveOptions::default();
+    if let Some(threads) = args.threads {
+        options.threads = threads;
+    }
+    options.limit = args.limit.or(default_limit);
+    match args.strategy {
+        Strategy::Propagation => (),
+        Strategy::Reduction => options.fill = FillStrategy::Reduction,
+        Strategy::Simple => {
+            options.selection = SelectionStrategy::Directional(SearchDirection::BottomRightTop);
+            options.fill = FillStrategy::Simple;
+        }
+        Strategy::DancingLinks => options.backend = Backend::DancingLinks,
+    }
+    return options;
+}
+
+/// Solutions found within the limit; no solution is not an error here.
+fn solutions<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    options: SolveOptions,
+) -> Result<Vec<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    return match solve(table, options) {
+        Ok(outcome) => Result::Ok(outcome.solutions),
+        Err(SudokuError::SearchExhausted) => Result::Ok(Vec::new()),
+        Err(error) => Result::Err(error),
+    };
+}
+
+/// Writes `table` in the output format. `puzzle` tells givens apart from
+/// solved values where the format shows the difference.
+fn write_table<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    args: &Args,
+    out: &mut dyn Write,
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    puzzle: &PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>,
+) -> Result<(), CommandError> {
+    let file = PuzzleFile { table: table.clone(), metadata: puzzle.metadata.clone() };
+    return match output_format(args) {
+        OutputFormat::Line => writeln!(out, "{}", table),
+        OutputFormat::Grid => write!(out, "{:?}", table),
+        OutputFormat::Marks => write!(out, "{}", write_pencil_marks(table)),
+        OutputFormat::Sdk => write!(out, "{}", write_sdk(&file)),
+        OutputFormat::SimpleSudoku => write!(out, "{}", write_ss(&file)),
+        OutputFormat::Svg => write!(out, "{}", render_svg(&puzzle.table, table, &SvgOptions::default())?),
+        OutputFormat::Code => {
+            let progress = if table.to_string() == puzzle.table.to_string() { Option::None } else { Option::Some(table) };
+            writeln!(out, "{}", code::encode(&puzzle.table, progress)?)
+        }
+    }.map_err(CommandError::from);
+}
+
+fn solve_command<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    args: &Args,
+    out: &mut dyn Write,
+    file: &PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>,
+) -> Result<Status, CommandError> {
+    let solutions = solutions(&file.table, solve_options(args, Option::Some(2)))?;
+    // Without an explicit limit the second solution only proves ambiguity.
+    let shown = if args.limit.is_some() { solutions.len() } else { 1 };
+    for solution in solutions.iter().take(shown) {
+        write_table(args, out, solution, file)?;
+    }
+    return Result::Ok(Status::of_count(solutions.len()));
+}
+
+fn count_command<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    args: &Args,
+    out: &mut dyn Write,
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> Result<Status, CommandError> {
+    let options = solve_options(args, Option::None);
+    let count = count_solutions_with(table, options.limit.unwrap_or(usize::MAX), options)?;
+    writeln!(out, "{}", count)?;
+    return Result::Ok(Status::of_count(count));
+}
+
+fn validate_command<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    args: &Args,
+    out: &mut dyn Write,
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> Result<Status, CommandError> {
+    let mut options = solve_options(args, Option::None);
+    options.limit = Option::Some(2);
+    let status = Status::of_count(solutions(table, options)?.len());
+    if status == Status::Solved {
+        writeln!(out, "valid")?;
+    }
+    return Result::Ok(status);
+}
+
+fn grade_command<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    out: &mut dyn Write,
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> Result<Status, CommandError> {
+    let grade = match grade(table) {
+        Ok(grade) => grade,
+        Err(SudokuError::SearchExhausted) => return Result::Ok(Status::Unsolvable),
+        Err(error) => return Result::Err(CommandError::from(error)),
+    };
+    if !grade.unique {
+        return Result::Ok(Status::MultipleSolutions);
+    }
+    writeln!(out, "{:?} (givens: {}, branches: {})", grade.difficulty, grade.givens, grade.branches)?;
+    return Result::Ok(Status::Solved);
+}
+
+fn explain_command<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    out: &mut dyn Write,
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> Result<Status, CommandError> {
+    let explanation = explain(table, &Technique::ALL)?;
+    for (i, step) in explanation.steps.iter().enumerate() {
+        writeln!(out, "{}. {}: {}", i + 1, step.technique, step.explanation)?;
+    }
+    writeln!(out, "{}", explanation.outcome)?;
+    if explanation.outcome == ExplainOutcome::RequiresGuessing {
+        write!(out, "{}", write_pencil_marks(&explanation.table))?;
+    }
+    return Result::Ok(match explanation.outcome {
+        ExplainOutcome::Solved => Status::Solved,
+        ExplainOutcome::RequiresGuessing => Status::RequiresGuessing,
+        ExplainOutcome::Contradiction => Status::Unsolvable,
+    });
+}
+
+fn generate<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    args: &Args,
+    out: &mut dyn Write,
+) -> Status {
+    let rng = match args.seed {
+        Some(seed) => Rng::new(seed),
+        None => Rng::from_time(),
+    };
+    let mut generator = Generator::new(rng);
+    for _ in 0..args.count {
+        let written = generator.puzzle::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>()
+            .map_err(CommandError::from)
+            .and_then(|puzzle| {
+                let file = PuzzleFile { table: puzzle, metadata: PuzzleMetadata::default() };
+                write_table(args, out, &file.table, &file)
+            });
+        if let Err(error) = written {
+            eprintln!("sudoku: {}", error);
+            return error.status();
+        }
+    }
+    return Status::Solved;
+}
+
+fn batch<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(args: &Args) -> Status {
+    if args.inputs.len() > 1 {
+        eprintln!("sudoku: batch reads a single file");
+        return Status::InvalidInput;
+    }
+    let input: Box<dyn BufRead> = match args.inputs.first().map(String::as_str) {
+        None | Some("-") => Box::new(BufReader::new(std::io::stdin())),
+        Some(path) => match std::fs::File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => {
+                eprintln!("{}: {}", path, error);
+                return Status::InvalidInput;
+            }
+        },
+    };
+    let mut output = match open_output(args) {
+        Ok(output) => output,
+        Err(error) => {
+            eprintln!("{}: {}", args.output.as_deref().unwrap_or("-"), error);
+            return Status::InvalidInput;
+        }
+    };
+    let mut options = BatchOptions{ solve: solve_options(args, Option::None), ..BatchOptions::default() };
+    if let Some(threads) = args.threads {
+        options.workers = threads;
+    }
+    let summary = match solve_batch::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(input, &mut output, options) {
+        Ok(summary) => summary,
+        Err(error) => {
+            eprintln!("sudoku: {}", error);
+            return Status::InvalidInput;
+        }
+    };
+    eprintln!(
+        "{} puzzles: {} solved, {} unsolvable, {} multiple, {} invalid",
+        summary.puzzles, summary.solved, summary.unsolvable, summary.multiple, summary.invalid
+    );
+    if summary.invalid > 0 {
+        return Status::InvalidInput;
+    }
+    if summary.multiple > 0 {
+        return Status::MultipleSolutions;
+    }
+    if summary.unsolvable > 0 {
+        return Status::Unsolvable;
+    }
+    return Status::Solved;
+}
+
+/// Puzzles of a booklet input: a file with one line format puzzle per line
+/// holds several, any other input a single one.
+fn read_collection<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: &str,
+) -> Result<Vec<PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>>, String> {
+    let text = read_text(input)?;
+    let lines: Vec<&str> = text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+    if lines.len() > 1 {
+        let tables: Result<Vec<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> = lines.iter().map(|line| line.parse()).collect();
+        if let Ok(tables) = tables {
+            return Result::Ok(tables.into_iter().map(|table| PuzzleFile { table: table, metadata: PuzzleMetadata::default() }).collect());
+        }
+    }
+    return Result::Ok(vec![parse_input(input, &text)?]);
+}
+
+fn booklet<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    args: &Args,
+    out: &mut dyn Write,
+) -> Status {
+    let inputs = if args.inputs.is_empty() { vec![String::from("-")] } else { args.inputs.clone() };
+    let mut files = Vec::new();
+    for input in inputs.iter() {
+        match read_collection::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(input) {
+            Ok(collection) => files.extend(collection),
+            Err(message) => {
+                eprintln!("{}: {}", input, message);
+                return Status::InvalidInput;
+            }
+        }
+    }
+    let mut options = BookletOptions::default();
+    if let Some(title) = &args.title {
+        options.title = title.clone();
+    }
+    if let Some(per_page) = args.per_page {
+        options.puzzles_per_page = per_page;
+    }
+    let written = render_booklet(&files, &options)
+        .map_err(CommandError::from)
+        .and_then(|html| Result::Ok(out.write_all(html.as_bytes())?));
+    if let Err(error) = written {
+        eprintln!("sudoku: {}", error);
+        return error.status();
+    }
+    return Status::Solved;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sudoku::Table4;
+
+    const EASY: &str = "009218000170096800040050006451060370000005009902370500600501000000049257094800013";
+
+    fn parse(line: &str) -> Args {
+        let arguments: Vec<String> = line.split_whitespace().map(String::from).collect();
+        return Args::parse(&arguments).unwrap();
+    }
+
+    fn temp_file(name: &str, text: &str) -> String {
+        let path = std::env::temp_dir().join(format!("sudoku-cli-{}-{}", std::process::id(), name));
+        std::fs::write(&path, text).unwrap();
+        return path.to_string_lossy().into_owned();
+    }
+
+    #[test]
+    fn test_status_codes() {
+        assert_eq!(
+            [
+                Status::Solved as i32, Status::Unsolvable as i32, Status::MultipleSolutions as i32,
+                Status::InvalidInput as i32, Status::RequiresGuessing as i32,
+            ],
+            [0, 1, 2, 3, 4]
+        );
+        assert_eq!(Status::of_count(0), Status::Unsolvable);
+        assert_eq!(Status::of_count(1), Status::Solved);
+        assert_eq!(Status::of_count(5), Status::MultipleSolutions);
+        assert_eq!(CommandError::from(SudokuError::SearchExhausted).status(), Status::Unsolvable);
+        assert_eq!(CommandError::from(SudokuError::Conflict(Vec::new())).status(), Status::InvalidInput);
+        assert_eq!(CommandError::from(io::Error::other("closed")).status(), Status::InvalidInput);
+        assert_eq!(Status::Unsolvable.max(Status::MultipleSolutions), Status::MultipleSolutions);
+        assert_eq!(Status::RequiresGuessing.max(Status::Solved), Status::RequiresGuessing);
+        assert_eq!(Status::RequiresGuessing.max(Status::Unsolvable), Status::Unsolvable);
+    }
+
+    #[test]
+    fn test_run_exit_statuses() {
+        let output = temp_file("out.txt", "");
+        let solved = temp_file("solved.txt", EASY);
+        let unsolvable = temp_file("unsolvable.txt", "12........3...4.");
+        let multiple = temp_file("multiple.txt", "................");
+        let invalid = temp_file("invalid.txt", "1x..");
+        let hard = temp_file("hard.txt", "800000000003600000070090200050007000000045700000100030001000068008500010090000400");
+        let run_line = |line: String| run(&line.split_whitespace().map(String::from).collect::<Vec<String>>());
+
+        assert_eq!(run_line(format!("solve {} -o {}", solved, output)), Status::Solved);
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "369218745175496832248753196451962378736185429982374561627531984813649257594827613\n"
+        );
+        assert_eq!(run_line(format!("validate {} -o {}", solved, output)), Status::Solved);
+        assert_eq!(run_line(format!("grade {} -o {}", solved, output)), Status::Solved);
+        assert_eq!(run_line(format!("grade --size 4 {} -o {}", multiple, output)), Status::MultipleSolutions);
+        assert_eq!(run_line(format!("grade --size 4 {} -o {}", unsolvable, output)), Status::Unsolvable);
+        assert_eq!(run_line(format!("solve --size 4 {} -o {}", unsolvable, output)), Status::Unsolvable);
+        assert_eq!(run_line(format!("count --size 4 {} -o {}", multiple, output)), Status::MultipleSolutions);
+        assert_eq!(std::fs::read_to_string(&output).unwrap(), "288\n");
+        assert_eq!(run_line(format!("validate --size 4 {} -o {}", multiple, output)), Status::MultipleSolutions);
+        assert_eq!(run_line(format!("solve --size 4 {} {} -o {}", invalid, multiple, output)), Status::InvalidInput);
+        assert_eq!(run_line(format!("solve --size 5 {}", solved)), Status::InvalidInput);
+        assert_eq!(run_line(format!("explain {} -o {}", solved, output)), Status::Solved);
+        assert!(std::fs::read_to_string(&output).unwrap().starts_with("1. Naked single: "));
+        assert_eq!(run_line(format!("explain {} -o {}", hard, output)), Status::RequiresGuessing);
+        assert!(std::fs::read_to_string(&output).unwrap().contains("requires guessing"));
+        assert_eq!(run_line(format!("explain {} {} -o {}", hard, invalid, output)), Status::InvalidInput);
+        for path in [output, solved, unsolvable, multiple, invalid, hard] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_detects_input_formats() {
+        let table: Table = EASY.parse().unwrap();
+        let file = PuzzleFile { table: table.clone(), metadata: PuzzleMetadata{ author: Option::Some(String::from("A")), ..PuzzleMetadata::default() } };
+        let line = temp_file("line.txt", EASY);
+        let grid = temp_file("grid.txt", &format!("{:?}", table));
+        let sdk = temp_file("puzzle.sdk", &write_sdk(&file));
+        let ss = temp_file("puzzle.SS", &write_ss(&file));
+        let shared = temp_file("code.txt", &code::encode(&table, Option::None).unwrap());
+        let short = temp_file("short.txt", "8000");
+        for path in [line.clone(), grid.clone(), sdk.clone(), ss.clone(), shared.clone()].iter() {
+            assert_eq!(read_input::<9, 3, 3>(path).unwrap().table.to_string(), table.to_string(), "{}", path);
+        }
+        assert_eq!(read_input::<9, 3, 3>(&sdk).unwrap().metadata.author, Option::Some(String::from("A")));
+        assert_eq!(read_input::<9, 3, 3>(&short).unwrap_err(), "parse error at position 4: expected 81 cells, found 4");
+        assert!(read_input::<9, 3, 3>("no-such-file.txt").is_err());
+        // Points with a single candidate would be read back as placed values.
+        let hard: Table = "800000000003600000070090200050007000000045700000100030001000068008500010090000400".parse().unwrap();
+        let marks = temp_file("marks.txt", &write_pencil_marks(&hard));
+        assert_eq!(write_pencil_marks(&read_input::<9, 3, 3>(&marks).unwrap().table), write_pencil_marks(&hard));
+        let broken = temp_file("broken.txt", "AQEJAxyz");
+        assert!(read_input::<9, 3, 3>(&broken).unwrap_err().contains("code"));
+        assert!(!looks_like_code(EASY, 81));
+        assert!(looks_like_code("AQEJA4AY_-", 81));
+        for path in [line, grid, sdk, ss, shared, short, marks, broken] {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_output_format_and_writing() {
+        assert_eq!(output_format(&parse("solve")), OutputFormat::Line);
+        assert_eq!(output_format(&parse("solve -o out.SDK")), OutputFormat::Sdk);
+        assert_eq!(output_format(&parse("solve -o out.ss")), OutputFormat::SimpleSudoku);
+        assert_eq!(output_format(&parse("solve -o out.svg")), OutputFormat::Svg);
+        assert_eq!(output_format(&parse("solve -o out.svg -f grid")), OutputFormat::Grid);
+
+        let puzzle = PuzzleFile {
+            table: Table4::from_rows([[1,0,0,0], [0,0,3,0], [0,0,0,0], [0,0,0,2]]),
+            metadata: PuzzleMetadata{ author: Option::Some(String::from("A")), ..PuzzleMetadata::default() },
+        };
+        let solution = solutions(&puzzle.table, SolveOptions::default()).unwrap().remove(0);
+        let written = |line: &str| {
+            let mut out = Vec::new();
+            write_table(&parse(line), &mut out, &solution, &puzzle).unwrap();
+            return String::from_utf8(out).unwrap();
+        };
+        assert_eq!(written("solve"), format!("{}\n", solution));
+        assert!(written("solve -f sdk").starts_with("#AA\n"));
+        assert!(written("solve -f svg").starts_with("<svg"));
+        let shared = code::decode::<4, 2, 2>(&written("solve -f code")).unwrap();
+        assert_eq!(shared.puzzle.to_string(), puzzle.table.to_string());
+        assert_eq!(shared.progress.unwrap().to_string(), solution.to_string());
+    }
+}
@@ -0,0 +1,43 @@
+use crate::point::{Axis, Point};
+use crate::validators::Conflict;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SudokuError {
+    /// A point lies outside of the table.
+    OutOfRange(Axis),
+    /// A value that is neither empty nor one of the table's possible values.
+    InvalidDigit { point: Point, value: u8 },
+    /// The table breaks the sudoku rules in the listed places.
+    Conflict(Vec<Conflict>),
+    /// Input could not be read; `position` is the zero based offset of the offending character.
+    Parse { position: usize, message: String },
+    /// The search finished without finding a solution.
+    SearchExhausted,
+}
+
+impl fmt::Display for SudokuError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SudokuError::OutOfRange(axis) => write!(f, "point is out of range on the {:?} axis", axis),
+            SudokuError::InvalidDigit { point, value } => {
+                write!(f, "invalid value {} at ({}, {})", value, point.x, point.y)
+            }
+            SudokuError::Conflict(conflicts) => {
+                write!(f, "table breaks the rules in {} place(s)", conflicts.len())?;
+                for conflict in conflicts.iter() {
+                    write!(
+                        f,
+                        "; {} at ({}, {}) and ({}, {}) in {:?}",
+                        conflict.value, conflict.first.x, conflict.first.y, conflict.second.x, conflict.second.y, conflict.house
+                    )?;
+                }
+                return Result::Ok(());
+            }
+            SudokuError::Parse { position, message } => write!(f, "parse error at position {}: {}", position, message),
+            SudokuError::SearchExhausted => write!(f, "search exhausted without finding a solution"),
+        }
+    }
+}
+
+impl std::error::Error for SudokuError {}
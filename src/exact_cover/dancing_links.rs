@@ -0,0 +1,206 @@
+/// Sparse exact cover matrix searched with Knuth's Algorithm X.
+///
+/// Node `0` is the root, nodes `1..=columns` are the column headers and
+/// every other node is a one of the matrix linked to its row and column
+/// neighbours. Covering a column unlinks it together with every row using
+/// it; uncovering relinks them in reverse order, so the matrix is back in
+/// its original state once a search returns.
+pub struct DancingLinks {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    row: Vec<usize>,
+    sizes: Vec<usize>,
+    nodes: usize,
+}
+
+impl DancingLinks {
+    pub fn new(columns: usize) -> DancingLinks {
+        let headers = columns + 1;
+        let mut links = DancingLinks {
+            left: Vec::with_capacity(headers),
+            right: Vec::with_capacity(headers),
+            up: Vec::with_capacity(headers),
+            down: Vec::with_capacity(headers),
+            column: Vec::with_capacity(headers),
+            row: Vec::with_capacity(headers),
+            sizes: vec![0; headers],
+            nodes: 0,
+        };
+        for i in 0..headers {
+            links.left.push(if i == 0 { columns } else { i - 1 });
+            links.right.push(if i == columns { 0 } else { i + 1 });
+            links.up.push(i);
+            links.down.push(i);
+            links.column.push(i);
+            links.row.push(usize::MAX);
+        }
+        return links;
+    }
+
+    /// Adds a row covering the given zero based `columns`.
+    pub fn add_row(&mut self, row: usize, columns: &[usize]) {
+        let mut first = Option::None;
+        for column in columns {
+            let header = column + 1;
+            let node = self.left.len();
+            self.column.push(header);
+            self.row.push(row);
+            self.up.push(self.up[header]);
+            self.down.push(header);
+            let above = self.up[header];
+            self.down[above] = node;
+            self.up[header] = node;
+            self.sizes[header] += 1;
+            match first {
+                None => {
+                    self.left.push(node);
+                    self.right.push(node);
+                    first = Option::Some(node);
+                }
+                Some(first) => {
+                    let last = self.left[first];
+                    self.left.push(last);
+                    self.right.push(first);
+                    self.right[last] = node;
+                    self.left[first] = node;
+                }
+            }
+        }
+    }
+
+    /// Search nodes visited by all searches so far.
+    pub fn nodes(&self) -> usize {
+        return self.nodes;
+    }
+
+    /// Calls `on_solution` with the rows of every exact cover until it returns `false`.
+    pub fn search<F>(&mut self, mut on_solution: F) where F: FnMut(&[usize]) -> bool {
+        let mut partial = Vec::new();
+        self.search_from(&mut partial, &mut on_solution);
+    }
+
+    fn search_from<F>(&mut self, partial: &mut Vec<usize>, on_solution: &mut F) -> bool where F: FnMut(&[usize]) -> bool {
+        self.nodes += 1;
+        if self.right[0] == 0 {
+            return on_solution(partial);
+        }
+        let mut chosen = self.right[0];
+        let mut column = self.right[chosen];
+        while column != 0 {
+            if self.sizes[column] < self.sizes[chosen] {
+                chosen = column;
+            }
+            column = self.right[column];
+        }
+        if self.sizes[chosen] == 0 {
+            return true;
+        }
+        self.cover(chosen);
+        let mut node = self.down[chosen];
+        let mut keep_going = true;
+        while node != chosen && keep_going {
+            partial.push(self.row[node]);
+            let mut other = self.right[node];
+            while other != node {
+                self.cover(self.column[other]);
+                other = self.right[other];
+            }
+            keep_going = self.search_from(partial, on_solution);
+            let mut other = self.left[node];
+            while other != node {
+                self.uncover(self.column[other]);
+                other = self.left[other];
+            }
+            partial.pop();
+            node = self.down[node];
+        }
+        self.uncover(chosen);
+        return keep_going;
+    }
+
+    fn cover(&mut self, header: usize) {
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = right;
+        self.left[right] = left;
+        let mut node = self.down[header];
+        while node != header {
+            let mut other = self.right[node];
+            while other != node {
+                let (up, down) = (self.up[other], self.down[other]);
+                self.down[up] = down;
+                self.up[down] = up;
+                self.sizes[self.column[other]] -= 1;
+                other = self.right[other];
+            }
+            node = self.down[node];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut node = self.up[header];
+        while node != header {
+            let mut other = self.left[node];
+            while other != node {
+                let (up, down) = (self.up[other], self.down[other]);
+                self.down[up] = other;
+                self.up[down] = other;
+                self.sizes[self.column[other]] += 1;
+                other = self.left[other];
+            }
+            node = self.up[node];
+        }
+        let (left, right) = (self.left[header], self.right[header]);
+        self.right[left] = header;
+        self.left[right] = header;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DancingLinks;
+
+    #[test]
+    fn test_finds_knuths_example() {
+        let mut links = DancingLinks::new(7);
+        links.add_row(0, &[2, 4, 5]);
+        links.add_row(1, &[0, 3, 6]);
+        links.add_row(2, &[1, 2, 5]);
+        links.add_row(3, &[0, 3]);
+        links.add_row(4, &[1, 6]);
+        links.add_row(5, &[3, 4, 6]);
+
+        let mut solutions = Vec::new();
+        links.search(|rows| {
+            let mut rows = rows.to_vec();
+            rows.sort();
+            solutions.push(rows);
+            return true;
+        });
+        assert_eq!(solutions, vec![vec![0, 3, 4]]);
+    }
+
+    #[test]
+    fn test_stops_when_asked() {
+        let mut links = DancingLinks::new(2);
+        links.add_row(0, &[0, 1]);
+        links.add_row(1, &[0]);
+        links.add_row(2, &[1]);
+
+        let mut found = 0;
+        links.search(|_| {
+            found += 1;
+            return false;
+        });
+        assert_eq!(found, 1);
+
+        let mut total = 0;
+        links.search(|_| {
+            total += 1;
+            return true;
+        });
+        assert_eq!(total, 2);
+    }
+}
@@ -0,0 +1,175 @@
+pub mod dancing_links;
+
+use crate::{Candidates, Selectable, Settable, SudokuTable, SquareTable};
+use crate::candidates::mask_values;
+use crate::error::SudokuError;
+use crate::point::Point;
+use dancing_links::DancingLinks;
+
+/// Result of a single exact cover search.
+pub struct CoverSearch<T> {
+    /// Solved tables, empty unless they were asked for.
+    pub solutions: Vec<T>,
+    pub solution_count: usize,
+    /// Search nodes visited by Algorithm X.
+    pub nodes: usize,
+}
+
+/// A table encoded as an exact cover problem.
+///
+/// Every placement of a value in a point is a row covering four columns:
+/// the point itself and the value in its row, its column and its zone.
+/// Points holding a value get a single row, empty points one row per
+/// candidate, so eliminated candidates are respected. Zones are taken from
+/// [`SudokuTable::zone_index`], any layout it reports works. A given above
+/// the table size is reported as [`SudokuError::InvalidDigit`].
+pub struct SudokuCover<T> {
+    table: T,
+    placements: Vec<(Point, u8)>,
+    row_columns: Vec<[usize; 4]>,
+    columns: usize,
+}
+
+impl<T> SudokuCover<T> where T: Selectable + Settable + SudokuTable + SquareTable + Candidates + Clone {
+    pub fn new(table: &T) -> Result<SudokuCover<T>, SudokuError> {
+        let size = table.dimensions();
+        let houses = size * size;
+        let mut placements = Vec::new();
+        let mut row_columns = Vec::new();
+        for y in 0..size {
+            for x in 0..size {
+                let point = Point{x: x, y: y};
+                let value = table.value_in_point(&point)?;
+                if value as usize > size {
+                    return Result::Err(SudokuError::InvalidDigit { point: point, value: value });
+                }
+                let values = if value != 0 {
+                    vec![value]
+                } else {
+                    mask_values(table.candidates_in_point(&point)?)
+                };
+                let zone = table.zone_index(&point)?;
+                for value in values {
+                    let digit = value as usize - 1;
+                    row_columns.push([
+                        y * size + x,
+                        houses + y * size + digit,
+                        2 * houses + x * size + digit,
+                        3 * houses + zone * size + digit,
+                    ]);
+                    placements.push((point.clone(), value));
+                }
+            }
+        }
+        return Result::Ok(SudokuCover {
+            table: table.clone(),
+            placements: placements,
+            row_columns: row_columns,
+            columns: 4 * houses,
+        });
+    }
+
+    /// Searches for up to `limit` solutions, or all of them for `None`.
+    /// Solved tables are only built when `collect` is set.
+    pub fn search(&self, limit: Option<usize>, collect: bool) -> Result<CoverSearch<T>, SudokuError> {
+        let mut links = DancingLinks::new(self.columns);
+        for (row, columns) in self.row_columns.iter().enumerate() {
+            links.add_row(row, columns);
+        }
+        let mut found: Vec<Vec<usize>> = Vec::new();
+        let mut solution_count = 0;
+        if limit != Option::Some(0) {
+            links.search(|rows| {
+                solution_count += 1;
+                if collect {
+                    found.push(rows.to_vec());
+                }
+                return limit.is_none_or(|limit| solution_count < limit);
+            });
+        }
+        let mut solutions = Vec::with_capacity(found.len());
+        for rows in found {
+            let mut solution = self.table.clone();
+            for row in rows {
+                let (point, value) = &self.placements[row];
+                if solution.value_in_point(point)? == 0 {
+                    solution = *solution.set_in_point(point, *value)?;
+                }
+            }
+            solutions.push(solution);
+        }
+        return Result::Ok(CoverSearch {
+            solutions: solutions,
+            solution_count: solution_count,
+            nodes: links.nodes(),
+        });
+    }
+
+    pub fn solutions(&self, limit: Option<usize>) -> Result<Vec<T>, SudokuError> {
+        return Result::Ok(self.search(limit, true)?.solutions);
+    }
+
+    pub fn count(&self, limit: Option<usize>) -> Result<usize, SudokuError> {
+        return Result::Ok(self.search(limit, false)?.solution_count);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table, Table6};
+
+    #[test]
+    fn test_solves_classic_table() {
+        let t = Table::new_from([
+            [0,0,0,0,0,0,0,1,2],
+            [0,0,0,0,3,5,0,0,0],
+            [0,0,0,6,0,0,0,7,0],
+            [7,0,0,0,0,0,3,0,0],
+            [0,0,0,4,0,0,8,0,0],
+            [1,0,0,0,0,0,0,0,0],
+            [0,0,0,1,2,0,0,0,0],
+            [0,8,0,0,0,0,0,4,0],
+            [0,5,0,0,0,0,6,0,0]
+        ]);
+        let solutions = SudokuCover::new(&t).unwrap().solutions(Option::None).unwrap();
+        assert_eq!(solutions.len(), 1);
+        assert!(solutions[0].is_filled());
+        assert_eq!(solutions[0].value_in_point(&Point{x: 7, y: 0}).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_counts_with_limit() {
+        let cover = SudokuCover::new(&Table6::empty()).unwrap();
+        assert_eq!(cover.count(Option::Some(5)).unwrap(), 5);
+        assert_eq!(cover.count(Option::Some(0)).unwrap(), 0);
+        assert_eq!(cover.solutions(Option::Some(3)).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_rejects_out_of_range_givens() {
+        let mut rows = [[0u8; 6]; 6];
+        rows[2][3] = 7;
+        let error = SudokuCover::new(&Table6::from_rows(rows)).err().unwrap();
+        assert_eq!(error, SudokuError::InvalidDigit { point: Point{x: 3, y: 2}, value: 7 });
+    }
+
+    #[test]
+    fn test_respects_eliminated_candidates() {
+        let t = Table6::from_rows([
+            [0,0,3,0,1,0],
+            [5,6,0,3,2,0],
+            [0,5,4,2,0,3],
+            [2,0,6,4,5,0],
+            [0,1,2,0,4,5],
+            [0,4,0,1,0,0],
+        ]);
+        let cover = SudokuCover::new(&t).unwrap();
+        let solution = cover.solutions(Option::None).unwrap().remove(0);
+        let point = Point{x: 0, y: 0};
+        let value = solution.value_in_point(&point).unwrap();
+
+        let eliminated = *t.eliminate_in_point(&point, value).unwrap();
+        assert_eq!(SudokuCover::new(&eliminated).unwrap().count(Option::None).unwrap(), 0);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{Candidates, Selectable, Settable, SudokuTable, SquareTable};
+use crate::candidates::{full_mask, mask_of, CandidateMask};
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::table::Table;
+use crate::techniques::{Step, StepFinder, Technique};
+use crate::validators::TableValidator;
+use crate::validators::sequence_validator::SequenceValidator;
+use std::fmt;
+
+/// How far the techniques got.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ExplainOutcome {
+    Solved,
+    /// No technique applies any more; going on needs trial and error.
+    RequiresGuessing,
+    /// A point lost all of its candidates or a value has no place left in a house.
+    Contradiction,
+}
+
+impl fmt::Display for ExplainOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            ExplainOutcome::Solved => write!(f, "solved"),
+            ExplainOutcome::RequiresGuessing => write!(f, "requires guessing"),
+            ExplainOutcome::Contradiction => write!(f, "contradiction"),
+        };
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Explanation<T = Table> {
+    pub steps: Vec<Step>,
+    pub outcome: ExplainOutcome,
+    /// The table once every step is applied.
+    pub table: T,
+}
+
+/// Solves `table` the way a person would, applying the first of
+/// `techniques` that finds a deduction and starting over after each step,
+/// so easier techniques are always preferred. Never branches: when no
+/// technique applies the outcome is [`ExplainOutcome::RequiresGuessing`].
+///
+/// Fails with [`SudokuError::InvalidDigit`] or [`SudokuError::Conflict`] when the
+/// givens are broken.
+pub fn explain<T>(table: &T, techniques: &[Technique]) -> Result<Explanation<T>, SudokuError>
+    where T: Selectable + Settable + SudokuTable + SquareTable + Candidates + Clone {
+    SequenceValidator{}.ensure_valid(table)?;
+    let mut table = table.clone();
+    let mut steps = Vec::new();
+    let outcome = loop {
+        if is_contradictory(&table)? {
+            break ExplainOutcome::Contradiction;
+        }
+        let mut found = Option::None;
+        for technique in techniques {
+            found = technique.find(&table)?;
+            if found.is_some() {
+                break;
+            }
+        }
+        match found {
+            Some(step) => {
+                table = step.apply(&table)?;
+                steps.push(step);
+            }
+            None if is_filled(&table)? => break ExplainOutcome::Solved,
+            None => break ExplainOutcome::RequiresGuessing,
+        }
+    };
+    return Result::Ok(Explanation { steps: steps, outcome: outcome, table: table });
+}
+
+fn is_filled(table: &(impl Selectable + SquareTable)) -> Result<bool, SudokuError> {
+    for y in 0..table.dimensions() {
+        for x in 0..table.dimensions() {
+            if table.value_in_point(&Point{x: x, y: y})? == 0 {
+                return Result::Ok(false);
+            }
+        }
+    }
+    return Result::Ok(true);
+}
+
+fn is_contradictory(table: &(impl Selectable + SudokuTable + SquareTable + Candidates)) -> Result<bool, SudokuError> {
+    let full = full_mask(table.dimensions());
+    for (_, points) in SequenceValidator::houses(table)? {
+        let mut covered: CandidateMask = 0;
+        for point in points.iter() {
+            let value = table.value_in_point(point)?;
+            let candidates = table.candidates_in_point(point)?;
+            if value == 0 && candidates == 0 {
+                return Result::Ok(true);
+            }
+            covered |= if value != 0 { mask_of(value) } else { candidates };
+        }
+        if covered != full {
+            return Result::Ok(true);
+        }
+    }
+    return Result::Ok(false);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exact_cover::SudokuCover;
+    use crate::generator::{Generator, Rng};
+    use crate::table::Table4;
+
+    #[test]
+    fn test_explains_easy_table() {
+        let t: Table = "009218000170096800040050006451060370000005009902370500600501000000049257094800013".parse().unwrap();
+        let explanation = explain(&t, &Technique::ALL).unwrap();
+        assert_eq!(explanation.outcome, ExplainOutcome::Solved);
+        assert_eq!(explanation.steps.len(), 81 - 38);
+        assert!(explanation.table.is_filled());
+        assert!(explanation.steps.iter().all(|step| step.placements.len() == 1 && !step.explanation.is_empty()));
+    }
+
+    #[test]
+    fn test_explains_subsets() {
+        let t: Table = "805009060000002000700080009008000000031060408600500000006850000000000270009000003".parse().unwrap();
+        let singles = explain(&t, &Technique::ALL[..2]).unwrap();
+        assert_eq!(singles.outcome, ExplainOutcome::RequiresGuessing);
+        let explanation = explain(&t, &Technique::ALL).unwrap();
+        assert_eq!(explanation.outcome, ExplainOutcome::Solved);
+        let pair = explanation.steps.iter().find(|step| step.technique == Technique::HiddenPair).unwrap();
+        assert!(pair.placements.is_empty());
+        assert!(!pair.eliminations.is_empty());
+    }
+
+    #[test]
+    fn test_reports_guessing_instead_of_branching() {
+        let t: Table = "800000000003600000070090200050007000000045700000100030001000068008500010090000400".parse().unwrap();
+        let explanation = explain(&t, &Technique::ALL).unwrap();
+        assert_eq!(explanation.outcome, ExplainOutcome::RequiresGuessing);
+        assert_eq!(explanation.outcome.to_string(), "requires guessing");
+        assert!(!explanation.table.is_filled());
+    }
+
+    #[test]
+    fn test_steps_agree_with_solution() {
+        let mut generator = Generator::new(Rng::new(7));
+        for _ in 0..20 {
+            let puzzle = generator.puzzle::<9, 3, 3>().unwrap();
+            let solution = SudokuCover::new(&puzzle).unwrap().solutions(Option::Some(1)).unwrap().remove(0);
+            for step in explain(&puzzle, &Technique::ALL).unwrap().steps {
+                for (point, value) in step.placements.iter() {
+                    assert_eq!(solution.value_in_point(point).unwrap(), *value, "{}", step.explanation);
+                }
+                for (point, value) in step.eliminations.iter() {
+                    assert_ne!(solution.value_in_point(point).unwrap(), *value, "{}", step.explanation);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_reports_contradiction() {
+        let t = Table4::from_rows([
+            [1,2,0,0],
+            [0,0,0,0],
+            [0,0,3,0],
+            [0,0,4,0],
+        ]);
+        assert_eq!(explain(&t, &Technique::ALL).unwrap().outcome, ExplainOutcome::Contradiction);
+    }
+}
@@ -1,8 +1,34 @@
 pub mod simple_filler;
-use crate::{SudokuTable, Selectable, Settable, SquareTable};
+pub mod propagation_filler;
+pub mod reduction_filler;
+use crate::{Candidates, SudokuTable, Selectable, Settable, SquareTable};
 use crate::point::Point;
+use crate::error::SudokuError;
+use simple_filler::SimpleFiller;
+use propagation_filler::PropagationFiller;
+use reduction_filler::ReductionFiller;
 
 
 pub trait Filler {
-    fn fill<T>(&self, t: &T, p: &Point) -> Option<Vec<T>> where T: SudokuTable + Settable + Selectable + SquareTable;
-}
\ No newline at end of file
+    fn fill<T>(&self, t: &T, p: &Point) -> Result<Option<Vec<T>>, SudokuError> where T: SudokuTable + Settable + Selectable + SquareTable + Candidates;
+}
+
+/// The fillers available from the solver options.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FillStrategy {
+    Simple,
+    #[default]
+    Propagation,
+    /// Propagation plus the eliminating human techniques.
+    Reduction,
+}
+
+impl Filler for FillStrategy {
+    fn fill<T>(&self, t: &T, p: &Point) -> Result<Option<Vec<T>>, SudokuError> where T: SudokuTable + Settable + Selectable + SquareTable + Candidates {
+        match self {
+            FillStrategy::Simple => SimpleFiller{}.fill(t, p),
+            FillStrategy::Propagation => PropagationFiller{}.fill(t, p),
+            FillStrategy::Reduction => ReductionFiller{}.fill(t, p),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use super::Filler;
+use super::simple_filler::SimpleFiller;
+use crate::point::Point;
+use crate::error::SudokuError;
+use crate::{Candidates, SudokuTable, Selectable, Settable, SquareTable};
+use crate::candidates::{full_mask, mask_of, mask_values, CandidateMask};
+use crate::validators::sequence_validator::SequenceValidator;
+
+/// Branches like [`SimpleFiller`], then places every naked and hidden single
+/// in each child and drops the children that turn out to be contradictory.
+pub struct PropagationFiller {}
+
+impl PropagationFiller {
+    /// Repeatedly places naked singles (a point with one possible value) and
+    /// hidden singles (a value with one possible point in a row, column or
+    /// zone) until none are left.
+    ///
+    /// Returns `None` when the table is contradictory: an empty point without
+    /// possible values or a missing value without a place in its house.
+    pub fn propagate<T>(&self, table: T) -> Result<Option<T>, SudokuError>
+        where T: Selectable + Settable + SudokuTable + SquareTable + Candidates {
+        let houses = SequenceValidator::houses(&table)?;
+        let full = full_mask(table.dimensions());
+        let mut table = table;
+        loop {
+            let mut changed = false;
+            for y in 0..table.dimensions() {
+                for x in 0..table.dimensions() {
+                    let point = Point{x: x, y: y};
+                    if table.value_in_point(&point)? != 0 {
+                        continue;
+                    }
+                    let candidates = table.candidates_in_point(&point)?;
+                    match candidates.count_ones() {
+                        0 => return Result::Ok(Option::None),
+                        1 => {
+                            table = *table.set_in_point(&point, candidates.trailing_zeros() as u8)?;
+                            changed = true;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            for (_, points) in houses.iter() {
+                let mut placed: CandidateMask = 0;
+                let mut once: CandidateMask = 0;
+                let mut twice: CandidateMask = 0;
+                for point in points.iter() {
+                    let value = table.value_in_point(point)?;
+                    if value != 0 {
+                        placed |= mask_of(value);
+                        continue;
+                    }
+                    let candidates = table.candidates_in_point(point)?;
+                    twice |= once & candidates;
+                    once |= candidates;
+                }
+                if placed | once != full {
+                    return Result::Ok(Option::None);
+                }
+                for value in mask_values(once & !twice & !placed) {
+                    let mut place = Option::None;
+                    for point in points.iter() {
+                        if table.candidates_in_point(point)? & mask_of(value) != 0 {
+                            place = Option::Some(point);
+                        }
+                    }
+                    match place {
+                        Some(point) => {
+                            table = *table.set_in_point(point, value)?;
+                            changed = true;
+                        }
+                        None => return Result::Ok(Option::None),
+                    }
+                }
+            }
+            if !changed {
+                return Result::Ok(Option::Some(table));
+            }
+        }
+    }
+}
+
+impl Filler for PropagationFiller {
+    fn fill<T>(&self, table: &T, point: &Point) -> Result<Option<Vec<T>>, SudokuError>
+        where T: Selectable + Settable + SudokuTable + SquareTable + Candidates {
+        let simple_filler = SimpleFiller{};
+        let children = match simple_filler.fill(table, point)? {
+            Some(children) => children,
+            None => return Result::Ok(Option::None),
+        };
+        let mut table_vec = Vec::<T>::with_capacity(children.len());
+        for child in children {
+            if let Some(propagated) = self.propagate(child)? {
+                table_vec.push(propagated);
+            }
+        }
+        if table_vec.is_empty() {
+            return Result::Ok(Option::None);
+        }
+        return Result::Ok(Option::Some(table_vec));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table, Table4};
+
+    #[test]
+    fn test_propagate_solves_easy_table() {
+        let t = Table::new_from([
+            [0,0,9,2,1,8,0,0,0],
+            [1,7,0,0,9,6,8,0,0],
+            [0,4,0,0,5,0,0,0,6],
+            [4,5,1,0,6,0,3,7,0],
+            [0,0,0,0,0,5,0,0,9],
+            [9,0,2,3,7,0,5,0,0],
+            [6,0,0,5,0,1,0,0,0],
+            [0,0,0,0,4,9,2,5,7],
+            [0,9,4,8,0,0,0,1,3]
+        ]);
+        let propagated = PropagationFiller{}.propagate(t).unwrap().unwrap();
+        assert!(propagated.is_filled());
+    }
+
+    #[test]
+    fn test_propagate_places_hidden_single() {
+        // 1 is blocked from every point of the first zone except (0, 0),
+        // which still has several possible values.
+        let mut fields = [[0u8; 9]; 9];
+        fields[1][4] = 1;
+        fields[2][7] = 1;
+        fields[4][1] = 1;
+        fields[7][2] = 1;
+        let propagated = PropagationFiller{}.propagate(Table::new_from(fields)).unwrap().unwrap();
+        assert_eq!(propagated.value_in_point(&Point{x: 0, y: 0}).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_propagate_detects_contradiction() {
+        let t = Table4::from_rows([
+            [1,2,0,0],
+            [0,0,0,0],
+            [0,0,3,0],
+            [0,0,4,0],
+        ]);
+        assert!(PropagationFiller{}.propagate(t).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_fill_returns_propagated_children() {
+        let t = Table4::from_rows([
+            [1,0,0,0],
+            [0,0,3,0],
+            [0,0,0,0],
+            [0,0,0,0],
+        ]);
+        let children = PropagationFiller{}.fill(&t, &Point{x: 1, y: 0}).unwrap().unwrap();
+        assert!(!children.is_empty());
+        for child in children.iter() {
+            assert_ne!(child.value_in_point(&Point{x: 1, y: 0}).unwrap(), 0);
+        }
+    }
+}
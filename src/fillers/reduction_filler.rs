@@ -0,0 +1,99 @@
+use super::Filler;
+use super::propagation_filler::PropagationFiller;
+use super::simple_filler::SimpleFiller;
+use crate::point::Point;
+use crate::error::SudokuError;
+use crate::{Candidates, SudokuTable, Selectable, Settable, SquareTable};
+use crate::techniques::{StepFinder, Technique};
+
+/// Branches like [`PropagationFiller`], but also applies the eliminating
+/// techniques of [`Technique::ELIMINATIONS`] to every child, so fewer and
+/// smaller children reach the bus.
+pub struct ReductionFiller {}
+
+impl ReductionFiller {
+    /// Alternates [`PropagationFiller::propagate`] with the first elimination
+    /// any technique finds until neither changes the table.
+    ///
+    /// Returns `None` when the table turns out to be contradictory.
+    pub fn reduce<T>(&self, table: T) -> Result<Option<T>, SudokuError>
+        where T: Selectable + Settable + SudokuTable + SquareTable + Candidates {
+        let propagation = PropagationFiller{};
+        let mut table = table;
+        loop {
+            table = match propagation.propagate(table)? {
+                Some(table) => table,
+                None => return Result::Ok(Option::None),
+            };
+            let mut step = Option::None;
+            for technique in Technique::ELIMINATIONS.iter() {
+                step = technique.find(&table)?;
+                if step.is_some() {
+                    break;
+                }
+            }
+            let step = match step {
+                Some(step) => step,
+                None => return Result::Ok(Option::Some(table)),
+            };
+            for (point, value) in step.eliminations.iter() {
+                table = *table.eliminate_in_point(point, *value)?;
+            }
+        }
+    }
+}
+
+impl Filler for ReductionFiller {
+    fn fill<T>(&self, table: &T, point: &Point) -> Result<Option<Vec<T>>, SudokuError>
+        where T: Selectable + Settable + SudokuTable + SquareTable + Candidates {
+        let simple_filler = SimpleFiller{};
+        let children = match simple_filler.fill(table, point)? {
+            Some(children) => children,
+            None => return Result::Ok(Option::None),
+        };
+        let mut table_vec = Vec::<T>::with_capacity(children.len());
+        for child in children {
+            if let Some(reduced) = self.reduce(child)? {
+                table_vec.push(reduced);
+            }
+        }
+        if table_vec.is_empty() {
+            return Result::Ok(Option::None);
+        }
+        return Result::Ok(Option::Some(table_vec));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table;
+    use crate::solver::{solve, SolveOptions};
+    use crate::fillers::FillStrategy;
+
+    #[test]
+    fn test_reduce_goes_past_singles() {
+        let t: Table = "000000000904607000076804100309701080008000300050308702007502610000403208000000000".parse().unwrap();
+        let propagated = PropagationFiller{}.propagate(t.clone()).unwrap().unwrap();
+        let reduced = ReductionFiller{}.reduce(t.clone()).unwrap().unwrap();
+        let mut propagated_candidates = 0;
+        let mut reduced_candidates = 0;
+        for y in 0..9 {
+            for x in 0..9 {
+                let point = Point{x: x, y: y};
+                propagated_candidates += propagated.candidates_in_point(&point).unwrap().count_ones();
+                reduced_candidates += reduced.candidates_in_point(&point).unwrap().count_ones();
+            }
+        }
+        assert!(reduced_candidates < propagated_candidates);
+
+        let options = SolveOptions{ threads: 1, ..SolveOptions::default() };
+        let propagation = solve(&t, options.clone()).unwrap();
+        let reduction = solve(&t, SolveOptions{ fill: FillStrategy::Reduction, ..options }).unwrap();
+        assert_eq!(reduction.solution_count, 1);
+        assert_eq!(reduction.solutions[0].to_string(), propagation.solutions[0].to_string());
+        // A hidden pair finishes the table before any branching.
+        assert_eq!(reduction.stats.branches, 0);
+        assert!(propagation.stats.branches > 0);
+    }
+}
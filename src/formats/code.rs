@@ -0,0 +1,320 @@
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::table::Table;
+use crate::{Selectable, SquareTable};
+
+/// Layout version written as the first byte of every code.
+pub const CODE_VERSION: u8 = 1;
+const PROGRESS_FLAG: u8 = 1;
+const HEADER_LENGTH: usize = 4;
+const CHECKSUM_LENGTH: usize = 2;
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// A puzzle as shared by a code: its givens and optionally the values
+/// entered on top of them so far.
+#[derive(Debug, Clone)]
+pub struct SharedPuzzle<T = Table> {
+    pub puzzle: T,
+    pub progress: Option<T>,
+}
+
+/// Packs `puzzle`, and the values of `progress` in its empty points, into
+/// a URL safe base64 code without padding.
+///
+/// The bytes are a header (version, flags, size and zone height), a bit
+/// per point telling givens apart, a bit per remaining point telling
+/// progress apart when present, the values of those points in as few bits
+/// as the size allows and a CRC-16 of everything before it.
+pub fn encode<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    puzzle: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    progress: Option<&Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>,
+) -> Result<String, SudokuError> {
+    let mut givens = Vec::new();
+    let mut entered = Vec::new();
+    let mut bits = BitWriter::default();
+    for point in points(SIZE) {
+        let value = checked_value(puzzle, &point)?;
+        bits.push(value != 0);
+        if value != 0 {
+            givens.push(value);
+        }
+    }
+    if let Some(progress) = progress {
+        for point in points(SIZE) {
+            if puzzle.value_in_point(&point)? != 0 {
+                continue;
+            }
+            let value = checked_value(progress, &point)?;
+            bits.push(value != 0);
+            if value != 0 {
+                entered.push(value);
+            }
+        }
+    }
+    let width = value_width(SIZE);
+    for value in givens.iter().chain(entered.iter()) {
+        bits.push_value((*value - 1) as usize, width);
+    }
+
+    let flags = if progress.is_some() { PROGRESS_FLAG } else { 0 };
+    let mut bytes = vec![CODE_VERSION, flags, SIZE as u8, ZONE_HEIGHT as u8];
+    bytes.extend(bits.bytes);
+    let checksum = crc16(&bytes);
+    bytes.extend_from_slice(&checksum.to_be_bytes());
+    return Result::Ok(to_base64(&bytes));
+}
+
+/// Reads a code written by [`encode`]. Codes that are not base64, fail
+/// their checksum, come from another version or describe another table
+/// layout are rejected with [`SudokuError::Parse`].
+pub fn decode<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    code: &str,
+) -> Result<SharedPuzzle<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    let code = code.trim();
+    let bytes = from_base64(code)?;
+    let corrupted = |message: &str| SudokuError::Parse { position: 0, message: String::from(message) };
+    if bytes.len() < HEADER_LENGTH + CHECKSUM_LENGTH {
+        return Result::Err(corrupted("code is too short"));
+    }
+    let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+    if crc16(content).to_be_bytes() != checksum {
+        return Result::Err(corrupted("checksum mismatch, the code is corrupted"));
+    }
+    if content[0] != CODE_VERSION {
+        return Result::Err(corrupted(&format!("unsupported code version {}", content[0])));
+    }
+    if content[2] as usize != SIZE || content[3] as usize != ZONE_HEIGHT {
+        return Result::Err(corrupted(&format!(
+            "code holds a {}x{} table with zones of {} rows, expected {}x{} with {}",
+            content[2], content[2], content[3], SIZE, SIZE, ZONE_HEIGHT
+        )));
+    }
+    let has_progress = content[1] & PROGRESS_FLAG != 0;
+
+    let truncated = || corrupted("code ends too early");
+    let mut bits = BitReader { bytes: &content[HEADER_LENGTH..], position: 0 };
+    let mut given_points = Vec::new();
+    let mut empty_points = Vec::new();
+    for point in points(SIZE) {
+        if bits.next().ok_or_else(truncated)? {
+            given_points.push(point);
+        } else {
+            empty_points.push(point);
+        }
+    }
+    let mut entered_points = Vec::new();
+    if has_progress {
+        for point in empty_points {
+            if bits.next().ok_or_else(truncated)? {
+                entered_points.push(point);
+            }
+        }
+    }
+    let width = value_width(SIZE);
+    let mut read_values = |points: &[Point], fields: &mut [[u8; SIZE]; SIZE]| -> Result<(), SudokuError> {
+        for point in points {
+            let value = bits.next_value(width).ok_or_else(truncated)? + 1;
+            if value > SIZE {
+                return Result::Err(corrupted(&format!("value {} is out of range", value)));
+            }
+            fields[point.y][point.x] = value as u8;
+        }
+        return Result::Ok(());
+    };
+    let mut fields = [[0u8; SIZE]; SIZE];
+    read_values(&given_points, &mut fields)?;
+    let puzzle = Table::from_rows(fields);
+    let progress = if has_progress {
+        read_values(&entered_points, &mut fields)?;
+        Option::Some(Table::from_rows(fields))
+    } else {
+        Option::None
+    };
+    return Result::Ok(SharedPuzzle { puzzle: puzzle, progress: progress });
+}
+
+fn points(size: usize) -> impl Iterator<Item = Point> {
+    return (0..size * size).map(move |i| Point{x: i % size, y: i / size});
+}
+
+fn checked_value(table: &(impl Selectable + SquareTable), point: &Point) -> Result<u8, SudokuError> {
+    let value = table.value_in_point(point)?;
+    if value as usize > table.dimensions() {
+        return Result::Err(SudokuError::InvalidDigit { point: point.clone(), value: value });
+    }
+    return Result::Ok(value);
+}
+
+/// Bits needed for the values `0..size`, stored as value minus one.
+fn value_width(size: usize) -> usize {
+    return (usize::BITS - (size - 1).leading_zeros()).max(1) as usize;
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    length: usize,
+}
+
+impl BitWriter {
+    fn push(&mut self, bit: bool) {
+        if self.length.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= 0x80 >> (self.length % 8);
+        }
+        self.length += 1;
+    }
+
+    fn push_value(&mut self, value: usize, width: usize) {
+        for shift in (0..width).rev() {
+            self.push(value >> shift & 1 == 1);
+        }
+    }
+}
+
+struct BitReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl BitReader<'_> {
+    fn next(&mut self) -> Option<bool> {
+        let byte = self.bytes.get(self.position / 8)?;
+        let bit = byte & (0x80 >> (self.position % 8)) != 0;
+        self.position += 1;
+        return Option::Some(bit);
+    }
+
+    fn next_value(&mut self, width: usize) -> Option<usize> {
+        let mut value = 0;
+        for _ in 0..width {
+            value = value << 1 | self.next()? as usize;
+        }
+        return Option::Some(value);
+    }
+}
+
+/// CRC-16/CCITT-FALSE.
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { crc << 1 ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    return crc;
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    let mut code = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = (chunk[0] as u32) << 16
+            | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+            | *chunk.get(2).unwrap_or(&0) as u32;
+        for i in 0..chunk.len() + 1 {
+            code.push(ALPHABET[(group >> (18 - 6 * i) & 0x3F) as usize] as char);
+        }
+    }
+    return code;
+}
+
+fn from_base64(code: &str) -> Result<Vec<u8>, SudokuError> {
+    let mut bytes = Vec::with_capacity(code.len() * 3 / 4);
+    let mut group: u32 = 0;
+    let mut pending = 0;
+    for (position, symbol) in code.chars().enumerate() {
+        let index = match ALPHABET.iter().position(|c| *c as char == symbol) {
+            Some(index) => index as u32,
+            None => return Result::Err(SudokuError::Parse {
+                position: position,
+                message: format!("unexpected character '{}' in code", symbol),
+            }),
+        };
+        group = group << 6 | index;
+        pending += 6;
+        if pending >= 8 {
+            pending -= 8;
+            bytes.push((group >> pending) as u8);
+            group &= (1 << pending) - 1;
+        }
+    }
+    if pending >= 6 {
+        return Result::Err(SudokuError::Parse {
+            position: code.chars().count(),
+            message: String::from("code has a dangling character"),
+        });
+    }
+    return Result::Ok(bytes);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table4, Table16};
+    use crate::solver::{solve, SolveOptions};
+
+    const PUZZLE: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+    #[test]
+    fn test_givens_round_trip() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let code = encode(&t, Option::None).unwrap();
+        assert!(code.len() < 40);
+        assert!(code.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+        let shared: SharedPuzzle = decode(&code).unwrap();
+        assert_eq!(shared.puzzle.to_string(), t.to_string());
+        assert!(shared.progress.is_none());
+    }
+
+    #[test]
+    fn test_progress_round_trip() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let solution = solve(&t, SolveOptions::default()).unwrap().solutions.remove(0);
+        let code = encode(&t, Option::Some(&solution)).unwrap();
+        let shared: SharedPuzzle = decode(&code).unwrap();
+        assert_eq!(shared.puzzle.to_string(), t.to_string());
+        assert_eq!(shared.progress.unwrap().to_string(), solution.to_string());
+
+        let mut line = String::from("123456789ABCDEFG");
+        line.push_str(&".".repeat(16 * 15));
+        let wide: Table16 = line.parse().unwrap();
+        let shared: SharedPuzzle<Table16> = decode(&encode(&Table16::empty(), Option::Some(&wide)).unwrap()).unwrap();
+        assert_eq!(shared.puzzle.to_string(), ".".repeat(256));
+        assert_eq!(shared.progress.unwrap().to_string(), wide.to_string());
+    }
+
+    #[test]
+    fn test_rejects_corrupted_codes() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let code = encode(&t, Option::None).unwrap();
+        let mut corrupted: Vec<char> = code.chars().collect();
+        corrupted[10] = if corrupted[10] == 'A' { 'B' } else { 'A' };
+        let corrupted: String = corrupted.into_iter().collect();
+        assert_eq!(
+            decode::<9, 3, 3>(&corrupted).unwrap_err(),
+            SudokuError::Parse { position: 0, message: String::from("checksum mismatch, the code is corrupted") }
+        );
+        assert_eq!(
+            decode::<9, 3, 3>(&code[..code.len() - 4]).unwrap_err(),
+            SudokuError::Parse { position: 0, message: String::from("checksum mismatch, the code is corrupted") }
+        );
+        match decode::<9, 3, 3>(&format!("{}!", code)) {
+            Err(SudokuError::Parse { position, .. }) => assert_eq!(position, code.len()),
+            other => panic!("unexpected result {:?}", other.map(|shared| shared.puzzle.to_string())),
+        }
+        assert!(decode::<4, 2, 2>(&code).unwrap_err().to_string().contains("9x9"));
+        assert!(decode::<9, 3, 3>("AAA").is_err());
+        assert!(decode::<9, 3, 3>(&encode(&Table4::empty(), Option::None).unwrap()).is_err());
+
+        let mut fields = [[0u8; 4]; 4];
+        fields[1][2] = 5;
+        assert_eq!(
+            encode(&Table4::from_rows(fields), Option::None).unwrap_err(),
+            SudokuError::InvalidDigit { point: Point{x: 2, y: 1}, value: 5 }
+        );
+    }
+}
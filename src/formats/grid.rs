@@ -0,0 +1,183 @@
+use super::symbol_value;
+use crate::error::SudokuError;
+use crate::table::Table;
+
+const BORDER_CHARACTERS: &str = "┌┐└┘├┤┬┴┼─═+-=";
+
+fn is_separator(c: char) -> bool {
+    return c == '│' || c == '|';
+}
+
+fn parse_error(position: usize, message: String) -> SudokuError {
+    return SudokuError::Parse { position: position, message: message };
+}
+
+/// Reads a framed grid back into a table: the box drawing output of
+/// `{:?}` or its ASCII variant drawn with `+---+` borders and `|` bars.
+///
+/// Border lines and blank lines are skipped. Every other line is a row
+/// with one `|` or `│` delimited segment per zone, holding its values
+/// separated by spaces. Empty points are blank, `.` or `0`; a blank point
+/// is recognised by its column in the segment, as laid out by `{:?}`.
+pub fn parse_grid<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: &str,
+) -> Result<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>, SudokuError> {
+    let mut fields = [[0u8; SIZE]; SIZE];
+    let mut rows = 0;
+    let mut offset = 0;
+    for line in input.split('\n') {
+        let chars: Vec<char> = line.chars().collect();
+        let line_start = offset;
+        offset += chars.len() + 1;
+        let is_border = chars.iter().all(|c| c.is_whitespace() || BORDER_CHARACTERS.contains(*c));
+        if is_border {
+            continue;
+        }
+        let bars: Vec<usize> = (0..chars.len()).filter(|i| is_separator(chars[*i])).collect();
+        let outside = chars.iter().enumerate()
+            .find(|(i, c)| !c.is_whitespace() && (bars.is_empty() || *i < bars[0] || *i > bars[bars.len() - 1]));
+        if let Some((i, c)) = outside {
+            return Result::Err(parse_error(line_start + i, format!("unexpected character '{}' outside of the grid", c)));
+        }
+        if rows == SIZE {
+            return Result::Err(parse_error(line_start, format!("expected {} rows, found more", SIZE)));
+        }
+        if bars.len() != SIZE / ZONE_WIDTH + 1 {
+            return Result::Err(parse_error(line_start, format!("expected {} zones in row, found {}", SIZE / ZONE_WIDTH, bars.len().saturating_sub(1))));
+        }
+        for (zone, window) in bars.windows(2).enumerate() {
+            let values = read_segment::<SIZE, ZONE_WIDTH>(&chars, window[0] + 1, window[1], line_start)?;
+            fields[rows][zone * ZONE_WIDTH..(zone + 1) * ZONE_WIDTH].copy_from_slice(&values);
+        }
+        rows += 1;
+    }
+    if rows < SIZE {
+        return Result::Err(parse_error(input.chars().count(), format!("expected {} rows, found {}", SIZE, rows)));
+    }
+    return Result::Ok(Table::from_rows(fields));
+}
+
+/// Values of the zone segment `chars[start..end]`.
+fn read_segment<const SIZE: usize, const ZONE_WIDTH: usize>(
+    chars: &[char],
+    start: usize,
+    end: usize,
+    line_start: usize,
+) -> Result<[u8; ZONE_WIDTH], SudokuError> {
+    // (offset of the first character, token) pairs.
+    let mut tokens: Vec<(usize, String)> = Vec::new();
+    for (i, c) in chars.iter().enumerate().take(end).skip(start) {
+        if c.is_whitespace() {
+            continue;
+        }
+        match tokens.last_mut() {
+            Some((first, token)) if *first + token.chars().count() == i => token.push(*c),
+            _ => tokens.push((i, c.to_string())),
+        }
+    }
+    if tokens.len() != ZONE_WIDTH {
+        // Blank points: fall back to the fixed layout written by `{:?}`.
+        let width = SIZE.to_string().len();
+        if end - start != ZONE_WIDTH * (width + 1) + 1 {
+            return Result::Err(parse_error(line_start + start, format!("expected {} values in zone, found {}", ZONE_WIDTH, tokens.len())));
+        }
+        tokens = Vec::new();
+        for cell in 0..ZONE_WIDTH {
+            let first = start + 1 + cell * (width + 1);
+            let token: String = chars[first..first + width].iter().filter(|c| !c.is_whitespace()).collect();
+            tokens.push((first, token));
+        }
+    }
+    let mut values = [0u8; ZONE_WIDTH];
+    for (cell, (first, token)) in tokens.iter().enumerate() {
+        values[cell] = read_value::<SIZE>(token).ok_or_else(|| {
+            parse_error(line_start + first, format!("unexpected value '{}'", token))
+        })?;
+    }
+    return Result::Ok(values);
+}
+
+fn read_value<const SIZE: usize>(token: &str) -> Option<u8> {
+    let value = match token {
+        "" | "." => 0,
+        _ => match token.parse::<u8>() {
+            Ok(value) => value,
+            Err(_) if token.chars().count() == 1 => symbol_value(token.chars().next()?)?,
+            Err(_) => return Option::None,
+        },
+    };
+    if value as usize > SIZE {
+        return Option::None;
+    }
+    return Option::Some(value);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table6, Table16};
+    use crate::point::Point;
+    use crate::Selectable;
+
+    const PUZZLE: &str = "009218000170096800040050006451060370000005009902370500600501000000049257094800013";
+
+    #[test]
+    fn test_reads_debug_output() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let grid = format!("{:?}", t);
+        let parsed: Table = parse_grid(&grid).unwrap();
+        assert_eq!(parsed.to_string(), t.to_string());
+    }
+
+    #[test]
+    fn test_reads_other_layouts() {
+        let mut t = Table6::empty();
+        for (x, y, value) in [(0, 0, 1), (5, 0, 6), (2, 3, 4), (4, 5, 2)].iter() {
+            t = *crate::Settable::set_in_point(&t, &Point{x: *x, y: *y}, *value).unwrap();
+        }
+        let parsed: Table6 = parse_grid(&format!("{:?}", t)).unwrap();
+        assert_eq!(parsed.to_string(), t.to_string());
+
+        let mut t = Table16::empty();
+        t = *crate::Settable::set_in_point(&t, &Point{x: 3, y: 7}, 16).unwrap();
+        t = *crate::Settable::set_in_point(&t, &Point{x: 15, y: 15}, 9).unwrap();
+        let parsed: Table16 = parse_grid(&format!("{:?}", t)).unwrap();
+        assert_eq!(parsed.to_string(), t.to_string());
+    }
+
+    #[test]
+    fn test_reads_ascii_grid() {
+        let grid = "
+            +-------+-------+-------+
+            | . . 9 | 2 1 8 | . . . |
+            | 1 7 . | . 9 6 | 8 . . |
+            | . 4 . | . 5 . | . . 6 |
+            +-------+-------+-------+
+            | 4 5 1 | . 6 . | 3 7 . |
+            | . . . | . . 5 | . . 9 |
+            | 9 . 2 | 3 7 . | 5 . . |
+            +-------+-------+-------+
+            | 6 . . | 5 . 1 | . . . |
+            | . . . | . 4 9 | 2 5 7 |
+            | . 9 4 | 8 . . | . 1 3 |
+            +-------+-------+-------+
+        ";
+        let parsed: Table = parse_grid(grid).unwrap();
+        assert_eq!(parsed.to_string(), PUZZLE.replace('0', "."));
+        assert_eq!(parsed.value_in_point(&Point{x: 8, y: 8}).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_reports_position() {
+        let grid = "| 1 2 | 3 4 |\n| 3 x | 1 2 |";
+        assert_eq!(
+            parse_grid::<4, 2, 2>(grid).unwrap_err(),
+            SudokuError::Parse { position: 18, message: String::from("unexpected value 'x'") }
+        );
+        match parse_grid::<4, 2, 2>("| 1 2 | 3 4 |\n| 3 4 | 1 2 |") {
+            Err(SudokuError::Parse { position, .. }) => assert_eq!(position, 27),
+            other => panic!("unexpected result {:?}", other.map(|t| t.to_string())),
+        }
+        assert!(parse_grid::<4, 2, 2>("| 1 2 3 4 |\n| 1 2 3 4 |\n| 1 2 3 4 |\n| 1 2 3 4 |").is_err());
+    }
+}
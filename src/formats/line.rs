@@ -0,0 +1,121 @@
+use super::{symbol_value, value_symbol};
+use crate::error::SudokuError;
+use crate::table::Table;
+use crate::Selectable;
+use crate::point::Point;
+use std::fmt;
+use std::str::FromStr;
+
+/// Reads the one line format: `SIZE * SIZE` symbols in row order, where
+/// `0` or `.` mark an empty point. Whitespace anywhere is ignored, so a
+/// puzzle split over several lines reads the same.
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> FromStr for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    type Err = SudokuError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut fields = [[0u8; SIZE]; SIZE];
+        let mut cells = 0;
+        for (position, symbol) in s.chars().enumerate() {
+            if symbol.is_whitespace() {
+                continue;
+            }
+            if cells == SIZE * SIZE {
+                return Result::Err(SudokuError::Parse {
+                    position: position,
+                    message: format!("expected {} cells, found more", SIZE * SIZE),
+                });
+            }
+            let value = match symbol {
+                '.' => 0,
+                _ => match symbol_value(symbol) {
+                    Some(value) if (value as usize) <= SIZE => value,
+                    _ => return Result::Err(SudokuError::Parse {
+                        position: position,
+                        message: format!("unexpected character '{}'", symbol),
+                    }),
+                },
+            };
+            fields[cells / SIZE][cells % SIZE] = value;
+            cells += 1;
+        }
+        if cells < SIZE * SIZE {
+            return Result::Err(SudokuError::Parse {
+                position: s.chars().count(),
+                message: format!("expected {} cells, found {}", SIZE * SIZE, cells),
+            });
+        }
+        return Result::Ok(Table::from_rows(fields));
+    }
+}
+
+/// Writes the one line format with `.` for empty points.
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> fmt::Display for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut line = String::with_capacity(SIZE * SIZE);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let value = self.value_in_point(&Point{x: x, y: y}).map_err(|_| fmt::Error)?;
+                line.push(if value == 0 { '.' } else { value_symbol(value) });
+            }
+        }
+        return write!(f, "{}", line);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table4, Table16};
+    use crate::SquareTable;
+
+    const PUZZLE: &str = "009218000170096800040050006451060370000005009902370500600501000000049257094800013";
+
+    #[test]
+    fn test_round_trip() {
+        let t: Table = PUZZLE.parse().unwrap();
+        assert_eq!(t.value_in_point(&Point{x: 2, y: 0}).unwrap(), 9);
+        assert_eq!(t.value_in_point(&Point{x: 0, y: 1}).unwrap(), 1);
+        assert_eq!(t.to_string(), PUZZLE.replace('0', "."));
+        let again: Table = t.to_string().parse().unwrap();
+        assert_eq!(again.to_string(), t.to_string());
+    }
+
+    #[test]
+    fn test_ignores_whitespace() {
+        let spaced: String = PUZZLE.as_bytes().chunks(9)
+            .map(|row| std::str::from_utf8(row).unwrap().replace('0', "."))
+            .collect::<Vec<String>>()
+            .join("\n  ");
+        let t: Table = spaced.parse().unwrap();
+        assert_eq!(t.to_string(), PUZZLE.replace('0', "."));
+    }
+
+    #[test]
+    fn test_reports_position() {
+        let mut broken = String::from(PUZZLE);
+        broken.replace_range(10..11, "x");
+        assert_eq!(
+            broken.parse::<Table>().unwrap_err(),
+            SudokuError::Parse { position: 10, message: String::from("unexpected character 'x'") }
+        );
+        match PUZZLE[..80].parse::<Table>() {
+            Err(SudokuError::Parse { position, .. }) => assert_eq!(position, 80),
+            other => panic!("unexpected result {:?}", other.map(|t| t.to_string())),
+        }
+        match format!("{}1", PUZZLE).parse::<Table>() {
+            Err(SudokuError::Parse { position, .. }) => assert_eq!(position, 81),
+            other => panic!("unexpected result {:?}", other.map(|t| t.to_string())),
+        }
+        assert!("1234123412341235".parse::<Table4>().is_err());
+    }
+
+    #[test]
+    fn test_letters_for_large_tables() {
+        let mut line = String::from("123456789abcdefg");
+        line.push_str(&".".repeat(16 * 15));
+        let t: Table16 = line.parse().unwrap();
+        assert_eq!(t.dimensions(), 16);
+        assert_eq!(t.value_in_point(&Point{x: 15, y: 0}).unwrap(), 16);
+        assert!(t.to_string().starts_with("123456789ABCDEFG."));
+    }
+}
@@ -0,0 +1,112 @@
+pub mod line;
+pub mod grid;
+pub mod pencil_marks;
+pub mod code;
+pub mod sadman;
+pub mod simple_sudoku;
+#[cfg(feature = "serde")]
+pub mod serialization;
+
+use crate::error::SudokuError;
+use crate::table::Table;
+
+/// Descriptive lines kept next to a puzzle by file formats.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PuzzleMetadata {
+    pub author: Option<String>,
+    pub description: Option<String>,
+    pub difficulty: Option<String>,
+    pub source: Option<String>,
+    pub date: Option<String>,
+    /// Free form comments in file order.
+    pub comments: Vec<String>,
+    /// Lines with metadata codes this crate has no field for, as code and
+    /// text, written back unchanged.
+    pub extra: Vec<(char, String)>,
+}
+
+/// A puzzle read from or written to a file, with its metadata.
+#[derive(Debug, Clone, Default)]
+pub struct PuzzleFile<T = Table> {
+    pub table: T,
+    pub metadata: PuzzleMetadata,
+}
+
+/// Reads the grid rows of a file format, each given as the zero based
+/// offset of the line and its text. Points are symbols, `.` or `0`;
+/// characters in `ignored` and whitespace are skipped.
+fn read_rows<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    rows: &[(usize, &str)],
+    ignored: &str,
+    end: usize,
+) -> Result<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>, SudokuError> {
+    let mut fields = [[0u8; SIZE]; SIZE];
+    for (y, (line_start, row)) in rows.iter().enumerate().take(SIZE) {
+        let mut x = 0;
+        for (offset, symbol) in row.chars().enumerate() {
+            if symbol.is_whitespace() || ignored.contains(symbol) {
+                continue;
+            }
+            let value = match symbol {
+                '.' => Option::Some(0),
+                _ => symbol_value(symbol).filter(|value| *value as usize <= SIZE),
+            };
+            match value {
+                Some(value) if x < SIZE => fields[y][x] = value,
+                Some(_) => return Result::Err(SudokuError::Parse {
+                    position: line_start + offset,
+                    message: format!("expected {} values in row, found more", SIZE),
+                }),
+                None => return Result::Err(SudokuError::Parse {
+                    position: line_start + offset,
+                    message: format!("unexpected character '{}'", symbol),
+                }),
+            }
+            x += 1;
+        }
+        if x < SIZE {
+            return Result::Err(SudokuError::Parse {
+                position: line_start + row.chars().count(),
+                message: format!("expected {} values in row, found {}", SIZE, x),
+            });
+        }
+    }
+    if rows.len() != SIZE {
+        let position = rows.get(SIZE).map(|(position, _)| *position).unwrap_or(end);
+        return Result::Err(SudokuError::Parse {
+            position: position,
+            message: format!("expected {} rows, found {}", SIZE, rows.len()),
+        });
+    }
+    return Result::Ok(Table::from_rows(fields));
+}
+
+/// Lines of `input` with the zero based offset of their first character.
+fn lines_with_offsets(input: &str) -> Vec<(usize, &str)> {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in input.split('\n') {
+        lines.push((offset, line.trim_end_matches('\r')));
+        offset += line.chars().count() + 1;
+    }
+    return lines;
+}
+
+/// Character used for `value` in text formats: `1`-`9`, then `A` for 10,
+/// `B` for 11 and so on, so every value of a 25x25 table fits one character.
+pub fn value_symbol(value: u8) -> char {
+    if value < 10 {
+        return (b'0' + value) as char;
+    }
+    return (b'A' + value - 10) as char;
+}
+
+/// Inverse of [`value_symbol`], letters in either case. `0` reads as empty.
+pub fn symbol_value(symbol: char) -> Option<u8> {
+    return match symbol {
+        '0'..='9' => Option::Some(symbol as u8 - b'0'),
+        'A'..='Z' => Option::Some(symbol as u8 - b'A' + 10),
+        'a'..='z' => Option::Some(symbol as u8 - b'a' + 10),
+        _ => Option::None,
+    };
+}
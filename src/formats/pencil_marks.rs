@@ -0,0 +1,215 @@
+use super::{symbol_value, value_symbol};
+use crate::candidates::{mask_of, mask_values, CandidateMask};
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::table::Table;
+use crate::{Candidates, Selectable};
+
+const BORDER_CHARACTERS: &str = "┌┐└┘├┤┬┴┼─═+-=.:'*";
+
+/// Reads a pencil mark grid: `SIZE * SIZE` whitespace separated entries in
+/// row order, each listing the possible values of its point, like `129`.
+///
+/// An entry with a single value is a placed value, `.` or `0` an empty
+/// point restricted by the rules alone and `-` an empty point without
+/// possible values. Vertical bars and border lines are ignored, so framed
+/// grids written by other programs read as well. Marks the givens already
+/// rule out are dropped.
+pub fn parse_pencil_marks<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: &str,
+) -> Result<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>, SudokuError> {
+    let mut fields = [[0u8; SIZE]; SIZE];
+    let mut marks: Vec<(Point, CandidateMask)> = Vec::new();
+    let mut cells = 0;
+    for (position, entry) in entries(input) {
+        if cells == SIZE * SIZE {
+            return Result::Err(SudokuError::Parse {
+                position: position,
+                message: format!("expected {} entries, found more", SIZE * SIZE),
+            });
+        }
+        let point = Point{x: cells % SIZE, y: cells / SIZE};
+        cells += 1;
+        match entry.as_str() {
+            "." | "0" => continue,
+            "-" => {
+                marks.push((point, 0));
+                continue;
+            }
+            _ => (),
+        }
+        let mut mask: CandidateMask = 0;
+        for (offset, symbol) in entry.chars().enumerate() {
+            match symbol_value(symbol) {
+                Some(value) if value != 0 && value as usize <= SIZE => mask |= mask_of(value),
+                _ => return Result::Err(SudokuError::Parse {
+                    position: position + offset,
+                    message: format!("unexpected character '{}'", symbol),
+                }),
+            }
+        }
+        if mask.count_ones() == 1 {
+            fields[point.y][point.x] = mask.trailing_zeros() as u8;
+        } else {
+            marks.push((point, mask));
+        }
+    }
+    if cells < SIZE * SIZE {
+        return Result::Err(SudokuError::Parse {
+            position: input.chars().count(),
+            message: format!("expected {} entries, found {}", SIZE * SIZE, cells),
+        });
+    }
+    let mut table = Table::from_rows(fields);
+    for (point, mask) in marks {
+        for value in mask_values(table.candidates_in_point(&point)? & !mask) {
+            table = *table.eliminate_in_point(&point, value)?;
+        }
+    }
+    return Result::Ok(table);
+}
+
+/// Entries of a pencil mark grid with the offset of their first character.
+fn entries(input: &str) -> Vec<(usize, String)> {
+    let mut entries: Vec<(usize, String)> = Vec::new();
+    let mut offset = 0;
+    for line in input.split('\n') {
+        let chars: Vec<char> = line.chars().collect();
+        let line_start = offset;
+        offset += chars.len() + 1;
+        // Rows may consist of `.` and `-` entries too, but never of longer runs.
+        let is_border = chars.iter().all(|c| c.is_whitespace() || BORDER_CHARACTERS.contains(*c))
+            && line.split_whitespace().any(|run| run.chars().count() > 1);
+        if is_border {
+            continue;
+        }
+        let mut previous_blank = true;
+        for (i, c) in chars.iter().enumerate() {
+            if c.is_whitespace() || *c == '|' || *c == '│' {
+                previous_blank = true;
+                continue;
+            }
+            if previous_blank {
+                entries.push((line_start + i, String::new()));
+            }
+            if let Some((_, entry)) = entries.last_mut() {
+                entry.push(*c);
+            }
+            previous_blank = false;
+        }
+    }
+    return entries;
+}
+
+/// Writes the pencil mark grid of `table`: placed values, and for empty
+/// points their possible values, aligned in columns and framed by zone.
+pub fn write_pencil_marks<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> String {
+    let mut entries = vec![vec![String::new(); SIZE]; SIZE];
+    for (y, row) in entries.iter_mut().enumerate() {
+        for (x, entry) in row.iter_mut().enumerate() {
+            let point = Point{x: x, y: y};
+            let value = table.value_in_point(&point).unwrap_or(0);
+            *entry = if value != 0 {
+                value_symbol(value).to_string()
+            } else {
+                let candidates = table.candidate_grid().candidates(&point);
+                match candidates {
+                    0 => String::from("-"),
+                    _ => mask_values(candidates).into_iter().map(value_symbol).collect(),
+                }
+            };
+        }
+    }
+    let width = entries.iter().flatten().map(String::len).max().unwrap_or(1);
+    let segment = "-".repeat(ZONE_WIDTH * (width + 1) + 1);
+    let border = vec![segment; SIZE / ZONE_WIDTH].join("+");
+    let mut lines = Vec::with_capacity(SIZE + SIZE / ZONE_HEIGHT);
+    for (y, row) in entries.iter().enumerate() {
+        if y != 0 && y % ZONE_HEIGHT == 0 {
+            lines.push(border.clone());
+        }
+        let zones: Vec<String> = row.chunks(ZONE_WIDTH)
+            .map(|zone| zone.iter().map(|entry| format!(" {:<width$}", entry, width = width)).collect::<String>() + " ")
+            .collect();
+        lines.push(zones.join("|").trim_end().to_string());
+    }
+    return lines.join("\n") + "\n";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table4;
+    use crate::solver::{solve, SolveOptions};
+    use crate::fillers::propagation_filler::PropagationFiller;
+
+    const PUZZLE: &str = "009218000170096800040050006451060370000005009902370500600501000000049257094800013";
+    const HARD: &str = "800000000003600000070090200050007000000045700000100030001000068008500010090000400";
+
+    #[test]
+    fn test_round_trip_keeps_eliminations() {
+        // Propagated, so every empty point has more than one possible value.
+        let t: Table = HARD.parse().unwrap();
+        let t = PropagationFiller{}.propagate(t).unwrap().unwrap();
+        let point = Point{x: 1, y: 0};
+        let t = *t.eliminate_in_point(&point, 4).unwrap();
+        let written = write_pencil_marks(&t);
+        assert!(written.starts_with(" 8       126     24569   | 2347 "));
+        assert!(written.contains("\n-----"));
+
+        let read: Table = parse_pencil_marks(&written).unwrap();
+        assert_eq!(read.to_string(), t.to_string());
+        assert!(read.candidate_grid() == t.candidate_grid());
+        assert_eq!(write_pencil_marks(&read), written);
+    }
+
+    #[test]
+    fn test_single_marks_are_placed() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let written = write_pencil_marks(&*t.eliminate_in_point(&Point{x: 0, y: 0}, 3).unwrap());
+        assert!(written.starts_with(" 5    36   9    | 2    1    8    | 47   34   45\n"));
+        let read: Table = parse_pencil_marks(&written).unwrap();
+        assert_eq!(read.value_in_point(&Point{x: 0, y: 0}).unwrap(), 5);
+    }
+
+    #[test]
+    fn test_marks_restrict_the_search() {
+        // Both solutions of the empty 4x4 corner are allowed by the rules;
+        // the marks keep only the one with 1 in the top left point.
+        let marks = "
+            1  2  34 34
+            34 34 12 12
+            .  .  .  .
+            .  .  .  .
+        ";
+        let t: Table4 = parse_pencil_marks(marks).unwrap();
+        assert_eq!(t.value_in_point(&Point{x: 0, y: 0}).unwrap(), 1);
+        assert_eq!(mask_values(t.candidates_in_point(&Point{x: 2, y: 1}).unwrap()), vec![1, 2]);
+        let outcome = solve(&t, SolveOptions::default()).unwrap();
+        for solution in outcome.solutions.iter() {
+            assert_eq!(solution.value_in_point(&Point{x: 1, y: 0}).unwrap(), 2);
+        }
+    }
+
+    #[test]
+    fn test_reads_framed_grids_and_reports_position() {
+        let framed = "
+            .------------.------------.
+            | 1  2       | 34  34     |
+            | 34 34      | 12  12     |
+            :------------+------------:
+            | .  .       | .   .      |
+            | -  .       | .   .      |
+            '------------'------------'
+        ";
+        let t: Table4 = parse_pencil_marks(framed).unwrap();
+        assert_eq!(t.candidates_in_point(&Point{x: 0, y: 3}).unwrap(), 0);
+        assert_eq!(
+            parse_pencil_marks::<4, 2, 2>("1 2 3 4\n3 4 1 2\n. . . .\n. 5 . .").unwrap_err(),
+            SudokuError::Parse { position: 26, message: String::from("unexpected character '5'") }
+        );
+        assert!(parse_pencil_marks::<4, 2, 2>("1 2 3 4").is_err());
+    }
+}
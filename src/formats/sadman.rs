@@ -0,0 +1,146 @@
+use super::{lines_with_offsets, read_rows, value_symbol, PuzzleFile, PuzzleMetadata};
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::table::Table;
+use crate::Selectable;
+
+/// Reads a SadMan Software `.sdk` file.
+///
+/// Metadata lines start with `#` and a letter: `#A` author, `#D`
+/// description, `#L` level, `#S` source, `#B` date and `#C` comment; lines
+/// with any other code are kept verbatim in `extra`. The grid follows, one row per line
+/// with `.` for empty points, optionally after a `[Puzzle]` header. Later
+/// sections such as `[State]` are ignored.
+pub fn read_sdk<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: &str,
+) -> Result<PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    let mut metadata = PuzzleMetadata::default();
+    let mut rows = Vec::with_capacity(SIZE);
+    let mut end = input.chars().count();
+    for (line_start, line) in lines_with_offsets(input) {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with('[') {
+            if !rows.is_empty() || !trimmed.eq_ignore_ascii_case("[puzzle]") {
+                end = line_start;
+                break;
+            }
+            continue;
+        }
+        if let Some(entry) = trimmed.strip_prefix('#') {
+            let mut chars = entry.chars();
+            let code = chars.next().unwrap_or(' ');
+            let text = chars.as_str().trim().to_string();
+            match code {
+                'A' => metadata.author = Option::Some(text),
+                'D' => metadata.description = Option::Some(text),
+                'L' => metadata.difficulty = Option::Some(text),
+                'S' => metadata.source = Option::Some(text),
+                'B' => metadata.date = Option::Some(text),
+                'C' => metadata.comments.push(text),
+                _ => metadata.extra.push((code, chars.as_str().to_string())),
+            }
+            continue;
+        }
+        rows.push((line_start, line));
+    }
+    let table = read_rows::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(&rows, "", end)?;
+    return Result::Ok(PuzzleFile { table: table, metadata: metadata });
+}
+
+pub fn write_sdk<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    file: &PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>,
+) -> String {
+    let metadata = &file.metadata;
+    let mut lines = Vec::new();
+    let fields = [
+        ('A', &metadata.author),
+        ('D', &metadata.description),
+        ('L', &metadata.difficulty),
+        ('S', &metadata.source),
+        ('B', &metadata.date),
+    ];
+    for (code, value) in fields.iter() {
+        if let Some(value) = value {
+            lines.push(format!("#{}{}", code, value));
+        }
+    }
+    for comment in metadata.comments.iter() {
+        lines.push(format!("#C{}", comment));
+    }
+    for (code, text) in metadata.extra.iter() {
+        lines.push(format!("#{}{}", code, text));
+    }
+    for y in 0..SIZE {
+        let row: String = (0..SIZE)
+            .map(|x| match file.table.value_in_point(&Point{x: x, y: y}).unwrap_or(0) {
+                0 => '.',
+                value => value_symbol(value),
+            })
+            .collect();
+        lines.push(row);
+    }
+    return lines.join("\n") + "\n";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SDK: &str = "\
+#ASadMan Software
+#DA classic
+#L Hard
+#CFirst comment
+#C Second comment
+#X kept as is
+[Puzzle]
+..9218...
+17..968..
+.4..5...6
+451.6.37.
+.....5..9
+9.237.5..
+6..5.1...
+....49257
+.948...13
+[State]
+whatever
+";
+
+    #[test]
+    fn test_reads_metadata_and_grid() {
+        let file: PuzzleFile = read_sdk(SDK).unwrap();
+        assert_eq!(file.table.to_string(), "..9218...17..968...4..5...6451.6.37......5..99.237.5..6..5.1.......49257.948...13");
+        assert_eq!(file.metadata.author, Option::Some(String::from("SadMan Software")));
+        assert_eq!(file.metadata.description, Option::Some(String::from("A classic")));
+        assert_eq!(file.metadata.difficulty, Option::Some(String::from("Hard")));
+        assert_eq!(file.metadata.comments, vec![String::from("First comment"), String::from("Second comment")]);
+        assert_eq!(file.metadata.extra, vec![('X', String::from(" kept as is"))]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let file: PuzzleFile = read_sdk(SDK).unwrap();
+        let written = write_sdk(&file);
+        assert!(written.starts_with("#ASadMan Software\n#DA classic\n#LHard\n#CFirst comment\n"));
+        assert!(written.contains("\n#X kept as is\n"));
+        assert!(!written.contains("#C#X"));
+        assert!(written.ends_with("\n.948...13\n"));
+        let again: PuzzleFile = read_sdk(&written).unwrap();
+        assert_eq!(again.metadata, file.metadata);
+        assert_eq!(again.table.to_string(), file.table.to_string());
+    }
+
+    #[test]
+    fn test_reports_position() {
+        let broken = "#Aauthor\n..92x8...\n";
+        assert_eq!(
+            read_sdk::<9, 3, 3>(broken).unwrap_err(),
+            SudokuError::Parse { position: 13, message: String::from("unexpected character 'x'") }
+        );
+        assert!(read_sdk::<9, 3, 3>("..9218...\n").is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! A [`Table`] is written as its rows of values, `0` marking an empty
+//! point, and read back from either those rows or the one line format.
+//! Use [`as_string`] to write the one line format instead. Candidate grids
+//! are written as the possible values of every point together with the
+//! values placed in each row, column and zone.
+//!
+//! Both forms hold only the values of a table, so reading them back
+//! rebuilds its candidates. Use [`with_candidates`] to keep eliminated
+//! candidates as well, or attach a deserialized grid to its values with
+//! [`Table::with_candidates`].
+
+use crate::candidates::{full_mask, mask_of, mask_values, CandidateGrid, CandidateMask};
+use crate::point::Point;
+use crate::table::Table;
+use crate::Selectable;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TableRepr {
+    Line(String),
+    Rows(Vec<Vec<u8>>),
+}
+
+fn rows_of<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> Vec<Vec<u8>> {
+    return (0..SIZE)
+        .map(|y| (0..SIZE).map(|x| table.value_in_point(&Point{x: x, y: y}).unwrap_or(0)).collect())
+        .collect();
+}
+
+/// Checks that `items` holds exactly `SIZE` entries and converts them.
+fn exactly<T, U: Copy, E: Error, const SIZE: usize>(
+    items: Vec<T>,
+    what: &str,
+    empty: U,
+    convert: impl Fn(T) -> Result<U, E>,
+) -> Result<[U; SIZE], E> {
+    if items.len() != SIZE {
+        return Result::Err(E::custom(format!("expected {} {}, found {}", SIZE, what, items.len())));
+    }
+    let mut converted = [empty; SIZE];
+    for (i, item) in items.into_iter().enumerate() {
+        converted[i] = convert(item)?;
+    }
+    return Result::Ok(converted);
+}
+
+fn value<E: Error, const SIZE: usize>(value: u8) -> Result<u8, E> {
+    if value as usize > SIZE {
+        return Result::Err(E::custom(format!("value {} is out of range for a {}x{} table", value, SIZE, SIZE)));
+    }
+    return Result::Ok(value);
+}
+
+/// Mask of `values`, which must all be possible values of the table.
+fn mask<E: Error, const SIZE: usize>(values: Vec<u8>) -> Result<CandidateMask, E> {
+    let mut mask = 0;
+    for v in values {
+        mask |= mask_of(value::<E, SIZE>(v)?);
+    }
+    return Result::Ok(mask & full_mask(SIZE));
+}
+
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> Serialize for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return rows_of(self).serialize(serializer);
+    }
+}
+
+impl<'de, const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> Deserialize<'de> for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return match TableRepr::deserialize(deserializer)? {
+            TableRepr::Line(line) => line.parse().map_err(D::Error::custom),
+            TableRepr::Rows(rows) => {
+                let fields = exactly::<_, _, _, SIZE>(rows, "rows", [0; SIZE], |row| {
+                    exactly::<_, _, _, SIZE>(row, "values in a row", 0, value::<D::Error, SIZE>)
+                })?;
+                Result::Ok(Table::from_rows(fields))
+            }
+        };
+    }
+}
+
+/// Serializes a table in the one line format, for `#[serde(with = "as_string")]`.
+pub mod as_string {
+    use crate::table::Table;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        return serializer.collect_str(table);
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        deserializer: D,
+    ) -> Result<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>, D::Error> {
+        return String::deserialize(deserializer)?.parse().map_err(D::Error::custom);
+    }
+}
+
+/// Serializes a table as its values together with its candidate grid, for
+/// `#[serde(with = "with_candidates")]`, so eliminations survive the trip.
+pub mod with_candidates {
+    use super::{exactly, rows_of, value};
+    use crate::candidates::CandidateGrid;
+    use crate::table::Table;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct Written<'a, const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> {
+        values: Vec<Vec<u8>>,
+        candidates: &'a CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    }
+
+    #[derive(Deserialize)]
+    struct Read<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> {
+        values: Vec<Vec<u8>>,
+        candidates: CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    }
+
+    pub fn serialize<S: Serializer, const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        return Written { values: rows_of(table), candidates: table.candidate_grid() }.serialize(serializer);
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        deserializer: D,
+    ) -> Result<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>, D::Error> {
+        let read = Read::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>::deserialize(deserializer)?;
+        let fields = exactly::<_, _, _, SIZE>(read.values, "rows", [0; SIZE], |row| {
+            exactly::<_, _, _, SIZE>(row, "values in a row", 0, value::<D::Error, SIZE>)
+        })?;
+        return Table::with_candidates(fields, &read.candidates).map_err(D::Error::custom);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CandidateGridRepr {
+    cells: Vec<Vec<Vec<u8>>>,
+    rows: Vec<Vec<u8>>,
+    columns: Vec<Vec<u8>>,
+    zones: Vec<Vec<u8>>,
+}
+
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> Serialize for CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let repr = CandidateGridRepr {
+            cells: (0..SIZE)
+                .map(|y| (0..SIZE).map(|x| mask_values(self.candidates(&Point{x: x, y: y}))).collect())
+                .collect(),
+            rows: (0..SIZE).map(|y| mask_values(self.row_mask(y))).collect(),
+            columns: (0..SIZE).map(|x| mask_values(self.column_mask(x))).collect(),
+            zones: (0..SIZE).map(|zone| mask_values(self.zone_mask(zone))).collect(),
+        };
+        return repr.serialize(serializer);
+    }
+}
+
+impl<'de, const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> Deserialize<'de> for CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = CandidateGridRepr::deserialize(deserializer)?;
+        let cells = exactly::<_, _, _, SIZE>(repr.cells, "rows", [0; SIZE], |row| {
+            exactly::<_, _, _, SIZE>(row, "points in a row", 0, mask::<D::Error, SIZE>)
+        })?;
+        return Result::Ok(CandidateGrid::from_masks(
+            cells,
+            exactly::<_, _, _, SIZE>(repr.rows, "rows", 0, mask::<D::Error, SIZE>)?,
+            exactly::<_, _, _, SIZE>(repr.columns, "columns", 0, mask::<D::Error, SIZE>)?,
+            exactly::<_, _, _, SIZE>(repr.zones, "zones", 0, mask::<D::Error, SIZE>)?,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::solver::{solve, SolveOptions, SolveOutcome};
+    use crate::table::Table4;
+    use crate::Candidates;
+
+    const PUZZLE: &str = "009218000170096800040050006451060370000005009902370500600501000000049257094800013";
+
+    #[test]
+    fn test_table_as_rows_and_string() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let json = serde_json::to_string(&t).unwrap();
+        assert!(json.starts_with("[[0,0,9,2,1,8,0,0,0],[1,7,0,"));
+        let from_rows: Table = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_rows.to_string(), t.to_string());
+        let from_line: Table = serde_json::from_str(&format!("\"{}\"", PUZZLE)).unwrap();
+        assert_eq!(from_line.to_string(), t.to_string());
+
+        #[derive(Serialize, Deserialize)]
+        struct Ticket {
+            #[serde(with = "as_string")]
+            puzzle: Table,
+        }
+        let json = serde_json::to_string(&Ticket { puzzle: t.clone() }).unwrap();
+        assert_eq!(json, format!("{{\"puzzle\":\"{}\"}}", t));
+        assert_eq!(serde_json::from_str::<Ticket>(&json).unwrap().puzzle.to_string(), t.to_string());
+    }
+
+    #[test]
+    fn test_only_values_round_trip() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let point = Point{x: 0, y: 0};
+        let eliminated = *t.eliminate_in_point(&point, 3).unwrap();
+        let back: Table = serde_json::from_str(&serde_json::to_string(&eliminated).unwrap()).unwrap();
+        assert_eq!(back.to_string(), eliminated.to_string());
+        assert_eq!(mask_values(eliminated.candidates_in_point(&point).unwrap()), vec![5]);
+        assert_eq!(mask_values(back.candidates_in_point(&point).unwrap()), vec![3, 5]);
+    }
+
+    #[test]
+    fn test_candidates_round_trip_with_table() {
+        #[derive(Debug, Serialize, Deserialize)]
+        struct Handover {
+            #[serde(with = "with_candidates")]
+            progress: Table,
+        }
+        let t: Table = PUZZLE.parse().unwrap();
+        let point = Point{x: 0, y: 0};
+        let eliminated = *t.eliminate_in_point(&point, 3).unwrap();
+        let json = serde_json::to_string(&Handover { progress: eliminated.clone() }).unwrap();
+        assert!(json.starts_with("{\"progress\":{\"values\":[[0,0,9,"));
+        let back = serde_json::from_str::<Handover>(&json).unwrap().progress;
+        assert_eq!(back.to_string(), eliminated.to_string());
+        assert_eq!(mask_values(back.candidates_in_point(&point).unwrap()), vec![5]);
+        assert!(back.candidate_grid() == eliminated.candidate_grid());
+
+        // Candidates that the values rule out are rejected.
+        let broken = json.replacen("[[[5],", "[[[1,5],", 1);
+        assert!(serde_json::from_str::<Handover>(&broken).unwrap_err().to_string().contains("invalid value 1 at (0, 0)"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_tables() {
+        assert!(serde_json::from_str::<Table4>("[[1,2,3,4],[0,0,0,0],[0,0,0,0]]").is_err());
+        assert!(serde_json::from_str::<Table4>("[[1,2,3,5],[0,0,0,0],[0,0,0,0],[0,0,0,0]]").is_err());
+        let error = serde_json::from_str::<Table4>("\"12x4............\"").unwrap_err();
+        assert!(error.to_string().contains("position 2"));
+    }
+
+    #[test]
+    fn test_candidate_grid_round_trip() {
+        let t: Table = PUZZLE.parse().unwrap();
+        let json = serde_json::to_string(t.candidate_grid()).unwrap();
+        assert!(json.starts_with("{\"cells\":[[[3,5],[3,6],[],"));
+        let grid: CandidateGrid<9, 3, 3> = serde_json::from_str(&json).unwrap();
+        assert!(&grid == t.candidate_grid());
+    }
+
+    #[test]
+    fn test_point_and_outcome() {
+        let point: Point = serde_json::from_str("{\"x\":3,\"y\":5}").unwrap();
+        assert_eq!(point, Point{x: 3, y: 5});
+
+        let t: Table = PUZZLE.parse().unwrap();
+        let outcome = solve(&t, SolveOptions::default()).unwrap();
+        let json = serde_json::to_value(&outcome).unwrap();
+        assert_eq!(json["solution_count"], 1);
+        assert!(json["stats"]["nodes"].as_u64().unwrap() > 0);
+        let back: SolveOutcome = serde_json::from_value(json).unwrap();
+        assert_eq!(back.solutions[0].to_string(), outcome.solutions[0].to_string());
+    }
+}
@@ -0,0 +1,142 @@
+use super::{lines_with_offsets, read_rows, value_symbol, PuzzleFile, PuzzleMetadata};
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::table::Table;
+use crate::Selectable;
+
+const SEPARATOR_CHARACTERS: &str = "-+*|";
+
+/// Reads a Simple Sudoku `.ss` file: one row per line with `.` for empty
+/// points and `|` between zones, `---+---` lines between bands and an
+/// optional `*---*` frame.
+///
+/// Lines starting with `!` are comments. `! Author: ...`, `! Description:`,
+/// `! Difficulty:`, `! Source:` and `! Date:` fill the metadata.
+pub fn read_ss<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    input: &str,
+) -> Result<PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    let mut metadata = PuzzleMetadata::default();
+    let mut rows = Vec::with_capacity(SIZE);
+    for (line_start, line) in lines_with_offsets(input) {
+        let trimmed = line.trim();
+        if let Some(comment) = trimmed.strip_prefix('!') {
+            let comment = comment.trim();
+            let (key, value) = match comment.split_once(':') {
+                Some((key, value)) => (key.trim().to_lowercase(), Option::Some(value.trim().to_string())),
+                None => (String::new(), Option::None),
+            };
+            match key.as_str() {
+                "author" => metadata.author = value,
+                "description" => metadata.description = value,
+                "difficulty" => metadata.difficulty = value,
+                "source" => metadata.source = value,
+                "date" => metadata.date = value,
+                _ => metadata.comments.push(comment.to_string()),
+            }
+            continue;
+        }
+        if trimmed.chars().all(|c| SEPARATOR_CHARACTERS.contains(c)) {
+            continue;
+        }
+        rows.push((line_start, line));
+    }
+    let table = read_rows::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>(&rows, "|", input.chars().count())?;
+    return Result::Ok(PuzzleFile { table: table, metadata: metadata });
+}
+
+pub fn write_ss<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    file: &PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>,
+) -> String {
+    let metadata = &file.metadata;
+    let mut lines = Vec::new();
+    let fields = [
+        ("Author", &metadata.author),
+        ("Description", &metadata.description),
+        ("Difficulty", &metadata.difficulty),
+        ("Source", &metadata.source),
+        ("Date", &metadata.date),
+    ];
+    for (key, value) in fields.iter() {
+        if let Some(value) = value {
+            lines.push(format!("! {}: {}", key, value));
+        }
+    }
+    for comment in metadata.comments.iter() {
+        lines.push(format!("! {}", comment));
+    }
+    let separator = vec!["-".repeat(ZONE_WIDTH); SIZE / ZONE_WIDTH].join("+");
+    for y in 0..SIZE {
+        if y != 0 && y % ZONE_HEIGHT == 0 {
+            lines.push(separator.clone());
+        }
+        let zones: Vec<String> = (0..SIZE / ZONE_WIDTH)
+            .map(|zone| {
+                (zone * ZONE_WIDTH..(zone + 1) * ZONE_WIDTH)
+                    .map(|x| match file.table.value_in_point(&Point{x: x, y: y}).unwrap_or(0) {
+                        0 => '.',
+                        value => value_symbol(value),
+                    })
+                    .collect()
+            })
+            .collect();
+        lines.push(zones.join("|"));
+    }
+    return lines.join("\n") + "\n";
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table6;
+
+    const SS: &str = "\
+! Author: Someone
+! Difficulty: Easy
+! from the weekly newsletter
+*-----------*
+|..9|218|...|
+|17.|.96|8..|
+|.4.|.5.|..6|
+|---+---+---|
+|451|.6.|37.|
+|...|..5|..9|
+|9.2|37.|5..|
+|---+---+---|
+|6..|5.1|...|
+|...|.49|257|
+|.94|8..|.13|
+*-----------*
+";
+
+    #[test]
+    fn test_reads_metadata_and_grid() {
+        let file: PuzzleFile = read_ss(SS).unwrap();
+        assert_eq!(file.table.to_string(), "..9218...17..968...4..5...6451.6.37......5..99.237.5..6..5.1.......49257.948...13");
+        assert_eq!(file.metadata.author, Option::Some(String::from("Someone")));
+        assert_eq!(file.metadata.difficulty, Option::Some(String::from("Easy")));
+        assert_eq!(file.metadata.comments, vec![String::from("from the weekly newsletter")]);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let file: PuzzleFile = read_ss(SS).unwrap();
+        let written = write_ss(&file);
+        assert!(written.starts_with("! Author: Someone\n! Difficulty: Easy\n! from the weekly newsletter\n..9|218|...\n"));
+        assert!(written.contains("\n---+---+---\n451|.6.|37.\n"));
+        let again: PuzzleFile = read_ss(&written).unwrap();
+        assert_eq!(again.metadata, file.metadata);
+        assert_eq!(again.table.to_string(), file.table.to_string());
+
+        let rectangular = PuzzleFile { table: Table6::empty(), metadata: PuzzleMetadata::default() };
+        assert_eq!(write_ss(&rectangular).lines().nth(2), Option::Some("---+---"));
+        assert_eq!(read_ss::<6, 2, 3>(&write_ss(&rectangular)).unwrap().table.to_string(), ".".repeat(36));
+    }
+
+    #[test]
+    fn test_reports_position() {
+        assert_eq!(
+            read_ss::<9, 3, 3>("..9|218|...\n17.|.96|8..|.\n").unwrap_err(),
+            SudokuError::Parse { position: 24, message: String::from("expected 9 values in row, found more") }
+        );
+    }
+}
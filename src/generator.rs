@@ -0,0 +1,146 @@
+use crate::error::SudokuError;
+use crate::exact_cover::SudokuCover;
+use crate::point::Point;
+use crate::table::Table;
+use crate::{Selectable, Settable};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Small xorshift generator; puzzles only need to look random, and a seed
+/// makes every generated puzzle reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // The all zero state would only ever produce zeros.
+        return Rng { state: seed ^ 0x9E37_79B9_7F4A_7C15 };
+    }
+
+    /// Seeded from the clock.
+    pub fn from_time() -> Rng {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or(0);
+        return Rng::new(nanos);
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        return self.state;
+    }
+
+    /// Uniform enough number in `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        return (self.next_u64() % bound as u64) as usize;
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i + 1);
+            items.swap(i, j);
+        }
+    }
+}
+
+/// Builds random puzzles with a unique solution.
+///
+/// A solved table found by the exact cover backend is scrambled with
+/// transformations that keep it valid: relabelling values and shuffling
+/// bands, stacks and the rows and columns inside them. Givens are then
+/// removed in random order as long as the solution stays unique.
+pub struct Generator {
+    rng: Rng,
+}
+
+impl Generator {
+    pub fn new(rng: Rng) -> Generator {
+        return Generator { rng: rng };
+    }
+
+    pub fn solution<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        &mut self,
+    ) -> Result<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>, SudokuError> {
+        let empty = Table::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>::empty();
+        let seed = match SudokuCover::new(&empty)?.solutions(Option::Some(1))?.pop() {
+            Some(seed) => seed,
+            None => return Result::Err(SudokuError::SearchExhausted),
+        };
+
+        let mut labels: Vec<u8> = (1..=SIZE as u8).collect();
+        self.rng.shuffle(&mut labels);
+        let rows = self.line_order(ZONE_HEIGHT, SIZE / ZONE_HEIGHT);
+        let columns = self.line_order(ZONE_WIDTH, SIZE / ZONE_WIDTH);
+        let mut fields = [[0u8; SIZE]; SIZE];
+        for (y, row) in fields.iter_mut().enumerate() {
+            for (x, field) in row.iter_mut().enumerate() {
+                let value = seed.value_in_point(&Point{x: columns[x], y: rows[y]})?;
+                *field = labels[value as usize - 1];
+            }
+        }
+        return Result::Ok(Table::from_rows(fields));
+    }
+
+    /// A random order of `groups` groups of `group_size` lines, shuffling the
+    /// groups and the lines within each of them.
+    fn line_order(&mut self, group_size: usize, groups: usize) -> Vec<usize> {
+        let mut group_order: Vec<usize> = (0..groups).collect();
+        self.rng.shuffle(&mut group_order);
+        let mut order = Vec::with_capacity(group_size * groups);
+        for group in group_order {
+            let mut lines: Vec<usize> = (group * group_size..(group + 1) * group_size).collect();
+            self.rng.shuffle(&mut lines);
+            order.extend(lines);
+        }
+        return order;
+    }
+
+    pub fn puzzle<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        &mut self,
+    ) -> Result<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>, SudokuError> {
+        let mut puzzle = self.solution::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>()?;
+        let mut points: Vec<Point> = Vec::with_capacity(SIZE * SIZE);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                points.push(Point{x: x, y: y});
+            }
+        }
+        self.rng.shuffle(&mut points);
+        for point in points.iter() {
+            let candidate = *puzzle.set_in_point(point, 0)?;
+            if SudokuCover::new(&candidate)?.count(Option::Some(2))? == 1 {
+                puzzle = candidate;
+            }
+        }
+        return Result::Ok(puzzle);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table6;
+    use crate::validators::TableValidator;
+    use crate::validators::sequence_validator::SequenceValidator;
+
+    #[test]
+    fn test_solution_is_valid() {
+        let solution = Generator::new(Rng::new(7)).solution::<9, 3, 3>().unwrap();
+        assert!(solution.is_filled());
+        assert!(SequenceValidator{}.validate(&solution).unwrap().is_valid());
+        let rectangular = Generator::new(Rng::new(7)).solution::<6, 2, 3>().unwrap();
+        assert!(SequenceValidator{}.validate(&rectangular).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_puzzle_is_unique_and_reproducible() {
+        let puzzle: Table = Generator::new(Rng::new(42)).puzzle().unwrap();
+        assert_eq!(SudokuCover::new(&puzzle).unwrap().count(Option::None).unwrap(), 1);
+        assert!(!puzzle.is_filled());
+        let again: Table = Generator::new(Rng::new(42)).puzzle().unwrap();
+        assert_eq!(again.to_string(), puzzle.to_string());
+        let other: Table6 = Generator::new(Rng::new(1)).puzzle().unwrap();
+        assert_eq!(SudokuCover::new(&other).unwrap().count(Option::None).unwrap(), 1);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::error::SudokuError;
+use crate::fillers::propagation_filler::PropagationFiller;
+use crate::point::Point;
+use crate::solver::{solve, SolveOptions};
+use crate::table::Table;
+use crate::validators::TableValidator;
+use crate::validators::sequence_validator::SequenceValidator;
+use crate::Selectable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    /// Solved by singles alone.
+    Easy,
+    Medium,
+    Hard,
+    Expert,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grade {
+    pub difficulty: Difficulty,
+    pub givens: usize,
+    /// Branches a single worker needed to find the solution and to look
+    /// for a second one, see [`crate::SolveStats::branches`].
+    pub branches: usize,
+    /// Whether `table` has exactly one solution.
+    pub unique: bool,
+}
+
+/// Rates `table` by how much guessing the default search needs once
+/// naked and hidden singles are exhausted. A single worker is used so
+/// the branch count, and with it the grade, is reproducible.
+pub fn grade<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> Result<Grade, SudokuError> {
+    SequenceValidator{}.ensure_valid(table)?;
+    let mut givens = 0;
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            if table.value_in_point(&Point{x: x, y: y})? != 0 {
+                givens += 1;
+            }
+        }
+    }
+    let propagated = PropagationFiller{}.propagate(table.clone())?;
+    if propagated.is_some_and(|t| t.is_filled()) {
+        return Result::Ok(Grade { difficulty: Difficulty::Easy, givens: givens, branches: 0, unique: true });
+    }
+    let options = SolveOptions{ threads: 1, limit: Option::Some(2), ..SolveOptions::default() };
+    let outcome = solve(table, options)?;
+    let branches = outcome.stats.branches;
+    let difficulty = match branches {
+        0..=10 => Difficulty::Medium,
+        11..=100 => Difficulty::Hard,
+        _ => Difficulty::Expert,
+    };
+    return Result::Ok(Grade { difficulty: difficulty, givens: givens, branches: branches, unique: outcome.solution_count == 1 });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_grades_easy_and_harder_tables() {
+        let easy: Table = "009218000170096800040050006451060370000005009902370500600501000000049257094800013".parse().unwrap();
+        let grade_easy = grade(&easy).unwrap();
+        assert_eq!(grade_easy.difficulty, Difficulty::Easy);
+        assert_eq!(grade_easy.givens, 38);
+
+        let hard: Table = "800000000003600000070090200050007000000045700000100030001000068008500010090000400".parse().unwrap();
+        let grade_hard = grade(&hard).unwrap();
+        assert!(grade_hard.difficulty > Difficulty::Easy);
+        assert!(grade_hard.branches > 0);
+        assert!(grade_easy.unique && grade_hard.unique);
+    }
+
+    #[test]
+    fn test_reports_multiple_solutions() {
+        let grade = grade(&crate::table::Table4::empty()).unwrap();
+        assert!(!grade.unique);
+    }
+
+    #[test]
+    fn test_rejects_unsolvable_table() {
+        let t = crate::table::Table4::from_rows([
+            [1,2,0,0],
+            [0,0,0,0],
+            [0,0,3,0],
+            [0,0,4,0],
+        ]);
+        assert_eq!(grade(&t), Result::Err(SudokuError::SearchExhausted));
+    }
+}
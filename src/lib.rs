@@ -0,0 +1,50 @@
+#![allow(clippy::needless_return, clippy::redundant_field_names)]
+
+use point::Point;
+
+pub mod error;
+pub mod candidates;
+pub mod table;
+pub mod point;
+pub mod validators;
+pub mod fillers;
+pub mod point_selection;
+pub mod bus;
+pub mod solver;
+pub mod exact_cover;
+pub mod batch;
+pub mod formats;
+pub mod render;
+pub mod generator;
+pub mod grader;
+pub mod techniques;
+pub mod explainer;
+
+pub use candidates::{CandidateGrid, CandidateMask};
+pub use error::SudokuError;
+pub use table::{Table, Table4, Table6, Table12, Table16, Table25};
+pub use solver::{count_solutions, count_solutions_with, has_unique_solution, has_unique_solution_with, solve, Backend, SolveOptions, SolveOutcome, SolveStats};
+
+pub trait Selectable {
+    fn value_in_point(&self, point: &Point) -> Result<u8, SudokuError>;
+}
+
+pub trait Settable {
+    fn set_in_point(&self, point: &Point, value: u8) -> Result<Box<Self>, SudokuError>;
+}
+
+pub trait SudokuTable {
+    fn points_in_zone(&self, point: &Point) -> Result<Vec<Point>, SudokuError>;
+    /// Zone of `point`, numbered left to right, top to bottom.
+    fn zone_index(&self, point: &Point) -> Result<usize, SudokuError>;
+    fn possible_values(&self) -> Vec<u8>;
+}
+
+pub trait Candidates {
+    fn candidates_in_point(&self, point: &Point) -> Result<CandidateMask, SudokuError>;
+    fn eliminate_in_point(&self, point: &Point, value: u8) -> Result<Box<Self>, SudokuError>;
+}
+
+pub trait SquareTable {
+    fn dimensions(&self) -> usize;
+}
@@ -2,22 +2,19 @@ use std::fmt::Debug;
 use std::cmp::PartialEq;
 use std::clone::Clone;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Axis {
     X, 
     Y
 }
-#[derive(Debug, PartialEq)]
-pub enum CoordinateError {
-    OutOfRange(Axis)
-}
-
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Point { pub x: usize, pub y: usize }
 
 impl PartialEq for Point {
     fn eq(&self, other: &Self) -> bool {
         self.x == other.x && self.y == other.y
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,10 @@
 use crate::point::Point;
-use crate::Selectable;
+use crate::error::SudokuError;
+use crate::{Candidates, Selectable, SudokuTable};
 use crate::SquareTable;
+use super::PointSelector;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SearchDirection {
     TopLeftBottom,
     TopLeftRight,
@@ -12,13 +16,13 @@ pub struct EmptyPointSearch {
 }
 
 impl EmptyPointSearch {
-    pub fn next_empty(&self, table: &(impl Selectable + SquareTable), search_direction: SearchDirection) -> Option<Point> {
+    pub fn next_empty(&self, table: &(impl Selectable + SquareTable), search_direction: SearchDirection) -> Result<Option<Point>, SudokuError> {
         match search_direction {
             SearchDirection::TopLeftBottom => {
                 for x in 0..table.dimensions() {
                     for y in 0..table.dimensions() {
-                        if table.value_in_point(&Point{x: x, y: y}).unwrap() == 0 {
-                            return Option::Some(Point{x: x, y: y});
+                        if table.value_in_point(&Point{x: x, y: y})? == 0 {
+                            return Result::Ok(Option::Some(Point{x: x, y: y}));
                         }
                     }
                 }
@@ -26,8 +30,8 @@ impl EmptyPointSearch {
             SearchDirection::TopLeftRight => {
                 for y in 0..table.dimensions() {
                     for x in 0..table.dimensions() {
-                        if table.value_in_point(&Point{x: x, y: y}).unwrap() == 0 {
-                            return Option::Some(Point{x: x, y: y});
+                        if table.value_in_point(&Point{x: x, y: y})? == 0 {
+                            return Result::Ok(Option::Some(Point{x: x, y: y}));
                         }
                     }
                 }
@@ -35,14 +39,26 @@ impl EmptyPointSearch {
             SearchDirection::BottomRightTop => {
                 for y in (0..table.dimensions()).rev() {
                     for x in (0..table.dimensions()).rev() {
-                        if table.value_in_point(&Point{x: x, y: y}).unwrap() == 0 {
-                            return Option::Some(Point{x: x, y: y});
+                        if table.value_in_point(&Point{x: x, y: y})? == 0 {
+                            return Result::Ok(Option::Some(Point{x: x, y: y}));
                         }
                     }
                 }
             }
         }
-        return Option::None;
+        return Result::Ok(Option::None);
+    }
+}
+
+
+/// [`PointSelector`] returning the first empty point in a fixed scan order.
+pub struct DirectionalSearch {
+    pub direction: SearchDirection,
+}
+
+impl PointSelector for DirectionalSearch {
+    fn select<T>(&self, table: &T) -> Result<Option<Point>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        return EmptyPointSearch{}.next_empty(table, self.direction);
     }
 }
 
@@ -50,7 +66,6 @@ impl EmptyPointSearch {
 #[cfg(test)]
 mod test {
     use crate::point::Point;
-    use crate::point::CoordinateError;
     use super::*;
     use crate::Selectable;
     use crate::SquareTable;
@@ -66,7 +81,7 @@ mod test {
     }
 
     impl Selectable for MockTable {
-        fn value_in_point(&self, point: &Point) -> Result<u8, CoordinateError> {
+        fn value_in_point(&self, point: &Point) -> Result<u8, SudokuError> {
             if point.x == 0 && point.y == 1 {
                 return Result::Ok(0);
             }
@@ -86,7 +101,7 @@ mod test {
         let t = MockTable{};
         let s = EmptyPointSearch{};
 
-        let p = s.next_empty(&t, SearchDirection::TopLeftBottom).unwrap();
+        let p = s.next_empty(&t, SearchDirection::TopLeftBottom).unwrap().unwrap();
         assert_eq!(p, Point{x: 0, y:1});
     }
 
@@ -96,7 +111,7 @@ mod test {
         let t = MockTable{};
         let s = EmptyPointSearch{};
 
-        let p = s.next_empty(&t, SearchDirection::TopLeftRight).unwrap();
+        let p = s.next_empty(&t, SearchDirection::TopLeftRight).unwrap().unwrap();
         assert_eq!(p, Point{x: 1, y:0});
     }
 }
@@ -1,5 +1,5 @@
 use crate::point::Point;
-use crate::point::CoordinateError;
+use crate::error::SudokuError;
 use crate::point::Axis;
 use crate::SquareTable;
 
@@ -13,12 +13,12 @@ pub enum SelectionType {
 }
 
 impl LinePointSelection {
-    pub fn get_points(&self, table: &impl SquareTable, point: &Point, selection_type: SelectionType) -> Result<Vec<Point>, CoordinateError> {
-        if point.x > table.dimensions() {
-            return Result::Err(CoordinateError::OutOfRange(Axis::X))
+    pub fn get_points(&self, table: &impl SquareTable, point: &Point, selection_type: SelectionType) -> Result<Vec<Point>, SudokuError> {
+        if point.x >= table.dimensions() {
+            return Result::Err(SudokuError::OutOfRange(Axis::X))
         }
-        if point.y > table.dimensions() {
-            return Result::Err(CoordinateError::OutOfRange(Axis::Y))
+        if point.y >= table.dimensions() {
+            return Result::Err(SudokuError::OutOfRange(Axis::Y))
         }
         let mut points = Vec::new();
         match selection_type {
@@ -42,7 +42,7 @@ mod test {
     use crate::point::Point;
     use super::*;
     use crate::SquareTable;
-    use crate::point::CoordinateError;
+    use crate::error::SudokuError;
     use crate::point::Axis;
 
     struct MockTable {
@@ -89,10 +89,10 @@ mod test {
         let s = LinePointSelection{};
 
         let y = s.get_points(&t, &Point{ x: 1, y: 5 }, SelectionType::Vertical).unwrap_err();
-        assert_eq!(y, CoordinateError::OutOfRange(Axis::Y));
+        assert_eq!(y, SudokuError::OutOfRange(Axis::Y));
 
         let x = s.get_points(&t, &Point{ x: 5, y: 2 }, SelectionType::Vertical).unwrap_err();
-        assert_eq!(x, CoordinateError::OutOfRange(Axis::X));
+        assert_eq!(x, SudokuError::OutOfRange(Axis::X));
     }
 }
 
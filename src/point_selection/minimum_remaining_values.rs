@@ -0,0 +1,123 @@
+use crate::point::Point;
+use crate::error::SudokuError;
+use crate::{Candidates, Selectable, SudokuTable, SquareTable};
+use super::PointSelector;
+
+/// Picks the empty point with the fewest possible values. Ties go to the
+/// point with more empty peers, since filling it constrains the most others.
+pub struct MinimumRemainingValues {
+
+}
+
+impl MinimumRemainingValues {
+    fn degree(table: &(impl Selectable + SudokuTable + SquareTable), point: &Point) -> Result<usize, SudokuError> {
+        let mut peers: Vec<Point> = table.points_in_zone(point)?;
+        for i in 0..table.dimensions() {
+            peers.push(Point{x: i, y: point.y});
+            peers.push(Point{x: point.x, y: i});
+        }
+        let mut degree = 0;
+        for (i, peer) in peers.iter().enumerate() {
+            if peer == point || peers[..i].contains(peer) {
+                continue;
+            }
+            if table.value_in_point(peer)? == 0 {
+                degree += 1;
+            }
+        }
+        return Result::Ok(degree);
+    }
+}
+
+impl PointSelector for MinimumRemainingValues {
+    fn select<T>(&self, table: &T) -> Result<Option<Point>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        let mut best: Option<(Point, u32, usize)> = Option::None;
+        for y in 0..table.dimensions() {
+            for x in 0..table.dimensions() {
+                let point = Point{x: x, y: y};
+                if table.value_in_point(&point)? != 0 {
+                    continue;
+                }
+                let remaining = table.candidates_in_point(&point)?.count_ones();
+                if remaining == 0 {
+                    return Result::Ok(Option::Some(point));
+                }
+                if let Some((_, best_remaining, _)) = best {
+                    if remaining > best_remaining {
+                        continue;
+                    }
+                }
+                let degree = MinimumRemainingValues::degree(table, &point)?;
+                let better = match best {
+                    Some((_, best_remaining, best_degree)) => remaining < best_remaining || degree > best_degree,
+                    None => true,
+                };
+                if better {
+                    best = Option::Some((point, remaining, degree));
+                }
+            }
+        }
+        return Result::Ok(best.map(|(point, _, _)| point));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table;
+    use crate::Selectable;
+
+    #[test]
+    fn test_selects_forced_point() {
+        let t = Table::new_from([
+            [1,2,3,4,5,6,7,8,0],
+            [0,0,0,0,0,0,0,0,0],
+            [0,0,0,0,0,0,0,0,0],
+            [0,0,0,0,0,0,0,0,0],
+            [0,0,0,0,0,0,0,0,0],
+            [0,0,0,0,0,0,0,0,0],
+            [0,0,0,0,0,0,0,0,0],
+            [0,0,0,0,0,0,0,0,0],
+            [0,0,0,0,0,0,0,0,0],
+        ]);
+        let p = MinimumRemainingValues{}.select(&t).unwrap().unwrap();
+        assert_eq!(p, Point{x: 8, y: 0});
+    }
+
+    #[test]
+    fn test_breaks_ties_by_degree() {
+        let t = Table::new_from([
+            [0,0,9,2,1,8,0,0,0],
+            [1,7,0,0,9,6,8,0,0],
+            [0,4,0,0,5,0,0,0,6],
+            [4,5,1,0,6,0,3,7,0],
+            [0,0,0,0,0,5,0,0,9],
+            [9,0,2,3,7,0,5,0,0],
+            [6,0,0,5,0,1,0,0,0],
+            [0,0,0,0,4,9,2,5,7],
+            [0,9,4,8,0,0,0,1,3]
+        ]);
+        let selected = MinimumRemainingValues{}.select(&t).unwrap().unwrap();
+        let selected_remaining = t.candidates_in_point(&selected).unwrap().count_ones();
+        let selected_degree = MinimumRemainingValues::degree(&t, &selected).unwrap();
+        for y in 0..9 {
+            for x in 0..9 {
+                let point = Point{x: x, y: y};
+                if t.value_in_point(&point).unwrap() != 0 {
+                    continue;
+                }
+                let remaining = t.candidates_in_point(&point).unwrap().count_ones();
+                assert!(remaining >= selected_remaining);
+                if remaining == selected_remaining {
+                    assert!(MinimumRemainingValues::degree(&t, &point).unwrap() <= selected_degree);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_returns_none_for_filled_table() {
+        let t = Table::new_from([[1u8; 9]; 9]);
+        assert_eq!(MinimumRemainingValues{}.select(&t).unwrap(), Option::None);
+    }
+}
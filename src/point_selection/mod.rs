@@ -1,2 +1,31 @@
 pub mod line_point_selection;
-pub mod empty_point_search;
\ No newline at end of file
+pub mod empty_point_search;
+pub mod minimum_remaining_values;
+
+use crate::{Candidates, Selectable, SudokuTable, SquareTable};
+use crate::error::SudokuError;
+use crate::point::Point;
+use empty_point_search::{DirectionalSearch, SearchDirection};
+use minimum_remaining_values::MinimumRemainingValues;
+
+/// Picks the empty point the solver branches on next.
+pub trait PointSelector {
+    fn select<T>(&self, table: &T) -> Result<Option<Point>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates;
+}
+
+/// The point selectors available from the solver options.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SelectionStrategy {
+    Directional(SearchDirection),
+    #[default]
+    MinimumRemainingValues,
+}
+
+impl PointSelector for SelectionStrategy {
+    fn select<T>(&self, table: &T) -> Result<Option<Point>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        match self {
+            SelectionStrategy::Directional(direction) => DirectionalSearch{ direction: *direction }.select(table),
+            SelectionStrategy::MinimumRemainingValues => MinimumRemainingValues{}.select(table),
+        }
+    }
+}
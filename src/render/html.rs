@@ -0,0 +1,169 @@
+use super::escape;
+use super::svg::{render_svg, SvgOptions};
+use crate::error::SudokuError;
+use crate::exact_cover::SudokuCover;
+use crate::formats::PuzzleFile;
+use crate::table::Table;
+use crate::validators::TableValidator;
+use crate::validators::sequence_validator::SequenceValidator;
+
+/// Settings for [`render_booklet`].
+#[derive(Debug, Clone)]
+pub struct BookletOptions {
+    pub title: String,
+    pub puzzles_per_page: usize,
+    /// Solutions are smaller, so more of them fit a page of the appendix.
+    pub solutions_per_page: usize,
+    /// Leave out the solutions appendix.
+    pub without_solutions: bool,
+}
+
+impl Default for BookletOptions {
+    fn default() -> Self {
+        BookletOptions {
+            title: String::from("Sudoku"),
+            puzzles_per_page: 4,
+            solutions_per_page: 12,
+            without_solutions: false,
+        }
+    }
+}
+
+const STYLE: &str = "\
+body{font-family:sans-serif;margin:0}\
+h1{text-align:center;margin:0 0 1em}\
+.page{page-break-after:always;break-after:page;padding:1.5cm;box-sizing:border-box}\
+.page:last-child{page-break-after:auto;break-after:auto}\
+.grid{display:grid;gap:1cm 1.5cm}\
+figure{margin:0;break-inside:avoid}\
+figure svg{width:100%;height:auto;display:block}\
+figcaption{text-align:center;margin-top:.3em}\
+.difficulty{color:#666}";
+
+/// Columns of a page holding `per_page` boards, rows filling up first.
+fn columns(per_page: usize) -> usize {
+    let mut columns = 1;
+    while columns * columns < per_page {
+        columns += 1;
+    }
+    return columns;
+}
+
+/// Renders a printable, self-contained HTML document with the puzzles of
+/// `files`, `puzzles_per_page` to a page, followed by their solutions.
+///
+/// Boards are drawn by [`render_svg`], so they look exactly like the
+/// stand-alone drawings. The description of a file titles its puzzle and
+/// its difficulty is printed next to it. Every puzzle must be solvable.
+pub fn render_booklet<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    files: &[PuzzleFile<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>],
+    options: &BookletOptions,
+) -> Result<String, SudokuError> {
+    let mut puzzles = Vec::with_capacity(files.len());
+    let mut solutions = Vec::with_capacity(files.len());
+    for (i, file) in files.iter().enumerate() {
+        let number = i + 1;
+        let mut caption = format!("{}", number);
+        if let Some(title) = &file.metadata.description {
+            caption.push_str(&format!(". {}", escape(title)));
+        }
+        if let Some(difficulty) = &file.metadata.difficulty {
+            caption.push_str(&format!(" <span class=\"difficulty\">({})</span>", escape(difficulty)));
+        }
+        puzzles.push(figure(&render_svg(&file.table, &file.table, &SvgOptions::default())?, &caption));
+        if !options.without_solutions {
+            SequenceValidator{}.ensure_valid(&file.table)?;
+            let solution = match SudokuCover::new(&file.table)?.solutions(Option::Some(1))?.pop() {
+                Some(solution) => solution,
+                None => return Result::Err(SudokuError::SearchExhausted),
+            };
+            let svg = render_svg(&file.table, &solution, &SvgOptions::default())?;
+            solutions.push(figure(&svg, &format!("{}", number)));
+        }
+    }
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        escape(&options.title), STYLE
+    );
+    write_pages(&mut html, &puzzles, options.puzzles_per_page, &escape(&options.title));
+    if !solutions.is_empty() {
+        write_pages(&mut html, &solutions, options.solutions_per_page, "Solutions");
+    }
+    html.push_str("</body>\n</html>\n");
+    return Result::Ok(html);
+}
+
+fn figure(svg: &str, caption: &str) -> String {
+    return format!("<figure>\n{}<figcaption>{}</figcaption>\n</figure>\n", svg, caption);
+}
+
+/// Splits `figures` into pages of `per_page`, the first page under `heading`.
+fn write_pages(html: &mut String, figures: &[String], per_page: usize, heading: &str) {
+    let per_page = per_page.max(1);
+    for (page, chunk) in figures.chunks(per_page).enumerate() {
+        html.push_str("<section class=\"page\">\n");
+        if page == 0 {
+            html.push_str(&format!("<h1>{}</h1>\n", heading));
+        }
+        html.push_str(&format!(
+            "<div class=\"grid\" style=\"grid-template-columns:repeat({},1fr)\">\n",
+            columns(per_page)
+        ));
+        for figure in chunk {
+            html.push_str(figure);
+        }
+        html.push_str("</div>\n</section>\n");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::formats::PuzzleMetadata;
+    use crate::generator::{Generator, Rng};
+
+    #[test]
+    fn test_pages_and_solutions() {
+        let mut generator = Generator::new(Rng::new(3));
+        let mut files: Vec<PuzzleFile> = Vec::new();
+        for i in 0..5 {
+            let metadata = PuzzleMetadata {
+                description: if i == 0 { Option::Some(String::from("Warm <up>")) } else { Option::None },
+                difficulty: Option::Some(String::from("Easy")),
+                ..PuzzleMetadata::default()
+            };
+            files.push(PuzzleFile { table: generator.puzzle().unwrap(), metadata: metadata });
+        }
+        let options = BookletOptions{ puzzles_per_page: 2, solutions_per_page: 4, ..BookletOptions::default() };
+        let html = render_booklet(&files, &options).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        // Three pages of puzzles and two of solutions.
+        assert_eq!(html.matches("<section class=\"page\">").count(), 5);
+        assert_eq!(html.matches("<svg").count(), 10);
+        assert_eq!(html.matches("<h1>").count(), 2);
+        assert!(html.contains("<figcaption>1. Warm &lt;up&gt; <span class=\"difficulty\">(Easy)</span></figcaption>"));
+        assert!(html.contains("repeat(2,1fr)"));
+        // Solutions draw every value, the puzzles only their givens.
+        let appendix = &html[html.find("<h1>Solutions</h1>").unwrap()..];
+        assert_eq!(appendix.matches("<text").count(), 5 * 81);
+    }
+
+    #[test]
+    fn test_without_solutions_and_unsolvable_puzzles() {
+        let unsolvable = PuzzleFile {
+            table: crate::table::Table4::from_rows([
+                [1,2,0,0],
+                [0,0,0,0],
+                [0,0,3,0],
+                [0,0,4,0],
+            ]),
+            metadata: PuzzleMetadata::default(),
+        };
+        let files = vec![unsolvable];
+        assert_eq!(render_booklet(&files, &BookletOptions::default()).unwrap_err(), SudokuError::SearchExhausted);
+        let options = BookletOptions{ without_solutions: true, ..BookletOptions::default() };
+        let html = render_booklet(&files, &options).unwrap();
+        assert!(!html.contains("Solutions"));
+    }
+}
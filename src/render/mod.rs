@@ -0,0 +1,117 @@
+pub mod svg;
+pub mod html;
+
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::{SquareTable, SudokuTable};
+
+/// Placement of the points of a table in a drawing, in user units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Geometry {
+    pub size: usize,
+    pub cell_size: f64,
+    /// Blank space around the grid, leaving room for the thick border.
+    pub margin: f64,
+}
+
+impl Geometry {
+    pub fn new(size: usize, cell_size: f64) -> Geometry {
+        return Geometry { size: size, cell_size: cell_size, margin: cell_size / 10.0 };
+    }
+
+    /// Width and height of the whole drawing.
+    pub fn extent(&self) -> f64 {
+        return self.size as f64 * self.cell_size + 2.0 * self.margin;
+    }
+
+    /// Top left corner of `point`.
+    pub fn origin(&self, point: &Point) -> (f64, f64) {
+        return (
+            self.margin + point.x as f64 * self.cell_size,
+            self.margin + point.y as f64 * self.cell_size,
+        );
+    }
+
+    /// Columns and rows of the pencil mark layout inside a point, as close
+    /// to square as possible: 3x3 for 9 values, 4x4 for 16.
+    pub fn mark_layout(&self) -> (usize, usize) {
+        let mut columns = 1;
+        while columns * columns < self.size {
+            columns += 1;
+        }
+        return (columns, self.size.div_ceil(columns));
+    }
+
+    /// Centre of the pencil mark of `value` inside `point`. Values outside
+    /// `1..=size` have no mark and give [`SudokuError::InvalidDigit`].
+    pub fn mark_centre(&self, point: &Point, value: u8) -> Result<(f64, f64), SudokuError> {
+        if value == 0 || value as usize > self.size {
+            return Result::Err(SudokuError::InvalidDigit { point: point.clone(), value: value });
+        }
+        let (columns, rows) = self.mark_layout();
+        let index = value as usize - 1;
+        let (x, y) = self.origin(point);
+        return Result::Ok((
+            x + (index % columns) as f64 * self.cell_size / columns as f64 + self.cell_size / columns as f64 / 2.0,
+            y + (index / columns) as f64 * self.cell_size / rows as f64 + self.cell_size / rows as f64 / 2.0,
+        ));
+    }
+}
+
+/// Zone of every point, `zones[y][x]`, as numbered by
+/// [`SudokuTable::zone_index`].
+pub fn zone_map(table: &(impl SudokuTable + SquareTable)) -> Result<Vec<Vec<usize>>, SudokuError> {
+    let size = table.dimensions();
+    let mut zones = vec![vec![0; size]; size];
+    for (y, row) in zones.iter_mut().enumerate() {
+        for (x, zone) in row.iter_mut().enumerate() {
+            *zone = table.zone_index(&Point{x: x, y: y})?;
+        }
+    }
+    return Result::Ok(zones);
+}
+
+/// Escapes text for XML content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    return escaped;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{zone_map, Geometry};
+    use crate::error::SudokuError;
+    use crate::point::Point;
+    use crate::table::Table6;
+
+    #[test]
+    fn test_mark_centre() {
+        let geometry = Geometry::new(9, 30.0);
+        assert_eq!(geometry.mark_centre(&Point{x: 0, y: 0}, 1).unwrap(), (8.0, 8.0));
+        assert_eq!(geometry.mark_centre(&Point{x: 0, y: 0}, 9).unwrap(), (28.0, 28.0));
+        for value in [0, 10] {
+            assert_eq!(
+                geometry.mark_centre(&Point{x: 2, y: 1}, value).unwrap_err(),
+                SudokuError::InvalidDigit { point: Point{x: 2, y: 1}, value: value }
+            );
+        }
+    }
+
+    #[test]
+    fn test_zone_map() {
+        let zones = zone_map(&Table6::empty()).unwrap();
+        assert_eq!(zones[0], vec![0, 0, 0, 1, 1, 1]);
+        assert_eq!(zones[3], vec![2, 2, 2, 3, 3, 3]);
+        assert_eq!(zones[5], vec![4, 4, 4, 5, 5, 5]);
+    }
+}
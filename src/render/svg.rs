@@ -0,0 +1,229 @@
+use super::{escape, zone_map, Geometry};
+use crate::candidates::mask_values;
+use crate::error::SudokuError;
+use crate::formats::value_symbol;
+use crate::point::Point;
+use crate::{Candidates, Selectable, SquareTable, SudokuTable};
+
+/// A coloured overlay on a point, or on one of its pencil marks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Highlight {
+    pub point: Point,
+    /// Highlights the pencil mark of this value instead of the whole point.
+    pub candidate: Option<u8>,
+    /// Any SVG paint, like `#ffd54f` or `gold`.
+    pub color: String,
+}
+
+/// Settings for [`render_svg`].
+#[derive(Debug, Clone)]
+pub struct SvgOptions {
+    pub cell_size: f64,
+    /// Draw the possible values of every empty point.
+    pub pencil_marks: bool,
+    pub highlights: Vec<Highlight>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 40.0,
+            pencil_marks: false,
+            highlights: Vec::new(),
+        }
+    }
+}
+
+const STYLE: &str = "\
+.thin{stroke:#999;stroke-width:1}\
+.thick{stroke:#000;stroke-width:3;stroke-linecap:square}\
+.given{font-weight:bold;fill:#000}\
+.solved{fill:#1a5fb4}\
+.mark{fill:#555}\
+text{font-family:sans-serif;text-anchor:middle;dominant-baseline:central}";
+
+/// Renders `state` as a standalone SVG document, which can also be inlined
+/// into HTML as is.
+///
+/// Values that are already in `puzzle` are drawn as givens, every other
+/// value of `state` as solved. Zone borders are drawn thick wherever two
+/// neighbouring points lie in different zones of `state`.
+///
+/// A highlighted candidate outside `1..=SIZE` is reported as
+/// [`SudokuError::InvalidDigit`].
+pub fn render_svg<T>(puzzle: &T, state: &T, options: &SvgOptions) -> Result<String, SudokuError>
+    where T: Selectable + SudokuTable + SquareTable + Candidates {
+    let size = state.dimensions();
+    let geometry = Geometry::new(size, options.cell_size);
+    for highlight in options.highlights.iter() {
+        state.value_in_point(&highlight.point)?;
+        if let Some(value) = highlight.candidate {
+            geometry.mark_centre(&highlight.point, value)?;
+        }
+    }
+    let extent = geometry.extent();
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{e}\" height=\"{e}\" viewBox=\"0 0 {e} {e}\">\
+         <style>{}</style>\
+         <rect x=\"0\" y=\"0\" width=\"{e}\" height=\"{e}\" fill=\"#fff\"/>\n",
+        STYLE, e = extent
+    );
+
+    for highlight in options.highlights.iter().filter(|h| h.candidate.is_none()) {
+        let (x, y) = geometry.origin(&highlight.point);
+        svg.push_str(&format!(
+            "<rect x=\"{}\" y=\"{}\" width=\"{c}\" height=\"{c}\" fill=\"{}\"/>\n",
+            x, y, escape(&highlight.color), c = geometry.cell_size
+        ));
+    }
+    let (columns, rows) = geometry.mark_layout();
+    let mark_radius = geometry.cell_size / columns.max(rows) as f64 / 2.0;
+    for highlight in options.highlights.iter() {
+        if let Some(value) = highlight.candidate {
+            let (x, y) = geometry.mark_centre(&highlight.point, value)?;
+            svg.push_str(&format!(
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"{}\"/>\n",
+                x, y, mark_radius, escape(&highlight.color)
+            ));
+        }
+    }
+
+    write_lines(&mut svg, state, &geometry)?;
+
+    for y in 0..size {
+        for x in 0..size {
+            let point = Point{x: x, y: y};
+            let value = state.value_in_point(&point)?;
+            if value != 0 {
+                let class = if puzzle.value_in_point(&point)? == value { "given" } else { "solved" };
+                let (left, top) = geometry.origin(&point);
+                svg.push_str(&format!(
+                    "<text class=\"{}\" x=\"{}\" y=\"{}\" font-size=\"{}\">{}</text>\n",
+                    class, left + geometry.cell_size / 2.0, top + geometry.cell_size / 2.0,
+                    geometry.cell_size * 0.6, value_symbol(value)
+                ));
+                continue;
+            }
+            let candidates = state.candidates_in_point(&point)?;
+            for value in mask_values(candidates) {
+                let highlighted = options.highlights.iter().any(|h| h.point == point && h.candidate == Option::Some(value));
+                if !options.pencil_marks && !highlighted {
+                    continue;
+                }
+                let (cx, cy) = geometry.mark_centre(&point, value)?;
+                svg.push_str(&format!(
+                    "<text class=\"mark\" x=\"{}\" y=\"{}\" font-size=\"{}\">{}</text>\n",
+                    cx, cy, mark_radius * 1.4, value_symbol(value)
+                ));
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    return Result::Ok(svg);
+}
+
+/// Thin lines between all points, thick ones between zones and around the grid.
+fn write_lines<T>(svg: &mut String, table: &T, geometry: &Geometry) -> Result<(), SudokuError>
+    where T: SudokuTable + SquareTable {
+    let size = geometry.size;
+    let zones = zone_map(table)?;
+    let cell = geometry.cell_size;
+    let (left, top) = geometry.origin(&Point{x: 0, y: 0});
+    let mut thin = String::new();
+    let mut thick = String::new();
+    for y in 0..size {
+        for x in 0..size {
+            let (cx, cy) = geometry.origin(&Point{x: x, y: y});
+            // Right edge of the point, then its bottom edge.
+            if x + 1 < size {
+                let path = if zones[y][x] != zones[y][x + 1] { &mut thick } else { &mut thin };
+                path.push_str(&format!("M{} {}v{}", cx + cell, cy, cell));
+            }
+            if y + 1 < size {
+                let path = if zones[y][x] != zones[y + 1][x] { &mut thick } else { &mut thin };
+                path.push_str(&format!("M{} {}h{}", cx, cy + cell, cell));
+            }
+        }
+    }
+    let side = size as f64 * cell;
+    thick.push_str(&format!("M{} {}h{}v{}h-{}z", left, top, side, side, side));
+    svg.push_str(&format!("<path class=\"thin\" fill=\"none\" d=\"{}\"/>\n", thin));
+    svg.push_str(&format!("<path class=\"thick\" fill=\"none\" d=\"{}\"/>\n", thick));
+    return Result::Ok(());
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table, Table6};
+    use crate::solver::{solve, SolveOptions};
+
+    const PUZZLE: &str = "009218000170096800040050006451060370000005009902370500600501000000049257094800013";
+
+    #[test]
+    fn test_givens_and_solved_values() {
+        let puzzle: Table = PUZZLE.parse().unwrap();
+        let solution = solve(&puzzle, SolveOptions::default()).unwrap().solutions.remove(0);
+        let svg = render_svg(&puzzle, &solution, &SvgOptions::default()).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"368\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("class=\"given\"").count(), 38);
+        assert_eq!(svg.matches("class=\"solved\"").count(), 81 - 38);
+        assert!(svg.contains("<text class=\"given\" x=\"104\" y=\"24\" font-size=\"24\">9</text>"));
+        assert!(!svg.contains("class=\"mark\""));
+    }
+
+    #[test]
+    fn test_zone_borders_follow_zones() {
+        let t = Table6::empty();
+        let svg = render_svg(&t, &t, &SvgOptions{ cell_size: 10.0, ..SvgOptions::default() }).unwrap();
+        let thick = svg.lines().find(|line| line.starts_with("<path class=\"thick\"")).unwrap();
+        // Zones are two rows by three columns: a vertical border after the
+        // third column and horizontal ones below the second and fourth rows.
+        assert!(thick.contains("M31 1v10"));
+        assert!(!thick.contains("M21 1v10"));
+        assert!(thick.contains("M1 21h10"));
+        assert!(!thick.contains("M1 31h10"));
+    }
+
+    #[test]
+    fn test_pencil_marks_and_highlights() {
+        let puzzle: Table = PUZZLE.parse().unwrap();
+        let options = SvgOptions {
+            pencil_marks: true,
+            highlights: vec![
+                Highlight { point: Point{x: 0, y: 0}, candidate: Option::None, color: String::from("#ffd54f") },
+                Highlight { point: Point{x: 1, y: 0}, candidate: Option::Some(6), color: String::from("\"red\"") },
+            ],
+            ..SvgOptions::default()
+        };
+        let svg = render_svg(&puzzle, &puzzle, &options).unwrap();
+        assert!(svg.contains("<rect x=\"4\" y=\"4\" width=\"40\" height=\"40\" fill=\"#ffd54f\"/>"));
+        assert!(svg.contains("fill=\"&quot;red&quot;\""));
+        let marks = svg.matches("class=\"mark\"").count();
+        let expected: u32 = (0..81)
+            .map(|i| puzzle.candidates_in_point(&Point{x: i % 9, y: i / 9}).unwrap().count_ones())
+            .sum();
+        assert_eq!(marks, expected as usize);
+
+        let only_highlighted = render_svg(&puzzle, &puzzle, &SvgOptions{ pencil_marks: false, ..options }).unwrap();
+        assert_eq!(only_highlighted.matches("class=\"mark\"").count(), 1);
+    }
+
+    #[test]
+    fn test_rejects_invalid_highlighted_candidates() {
+        let puzzle: Table = PUZZLE.parse().unwrap();
+        for value in [0, 10].iter() {
+            let options = SvgOptions {
+                highlights: vec![
+                    Highlight { point: Point{x: 1, y: 0}, candidate: Option::Some(*value), color: String::from("red") },
+                ],
+                ..SvgOptions::default()
+            };
+            assert_eq!(
+                render_svg(&puzzle, &puzzle, &options).unwrap_err(),
+                SudokuError::InvalidDigit { point: Point{x: 1, y: 0}, value: *value }
+            );
+        }
+    }
+}
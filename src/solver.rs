@@ -0,0 +1,440 @@
+use crate::bus::table_bus::TableBus;
+use crate::fillers::{Filler, FillStrategy};
+use crate::fillers::propagation_filler::PropagationFiller;
+use crate::fillers::reduction_filler::ReductionFiller;
+use crate::point_selection::{PointSelector, SelectionStrategy};
+use crate::error::SudokuError;
+use crate::exact_cover::SudokuCover;
+use crate::table::Table;
+use crate::validators::TableValidator;
+use crate::validators::sequence_validator::SequenceValidator;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Algorithms able to solve a table.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Backend {
+    /// Parallel search spreading branches over the [`TableBus`].
+    #[default]
+    Search,
+    /// Single threaded Algorithm X over an exact cover encoding of the table.
+    DancingLinks,
+}
+
+/// Settings for a single call to [`solve`].
+#[derive(Debug, Clone)]
+pub struct SolveOptions {
+    /// Number of workers pulling tables from the bus.
+    pub threads: usize,
+    /// How workers pick the next empty point to branch on.
+    pub selection: SelectionStrategy,
+    /// How workers expand a table into its children.
+    pub fill: FillStrategy,
+    pub backend: Backend,
+    /// Stop as soon as this many solutions are found; `None` enumerates all of them.
+    pub limit: Option<usize>,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        SolveOptions {
+            threads: 16,
+            selection: SelectionStrategy::default(),
+            fill: FillStrategy::default(),
+            backend: Backend::default(),
+            limit: Option::None,
+        }
+    }
+}
+
+/// Counters collected while searching.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveStats {
+    /// Tables taken from the bus and examined by a worker, or search nodes
+    /// visited by the exact cover backend.
+    pub nodes: usize,
+    /// Child tables pushed back to the bus; always zero for exact cover.
+    pub branches: usize,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveOutcome<T = Table> {
+    pub solutions: Vec<T>,
+    pub solution_count: usize,
+    pub stats: SolveStats,
+}
+
+/// Solves `table` on a dedicated runtime and blocks until the search is over.
+///
+/// Fails with [`SudokuError::InvalidDigit`] or [`SudokuError::Conflict`] when the
+/// givens are broken and with [`SudokuError::SearchExhausted`] when there is no solution.
+/// Must not be called from within a tokio runtime; use [`solve_async`] there.
+pub fn solve<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    options: SolveOptions,
+) -> Result<SolveOutcome<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    if options.backend == Backend::DancingLinks {
+        return require_solution(search_exact_cover(table, options.limit)?);
+    }
+    return runtime().block_on(solve_async(table, options));
+}
+
+pub async fn solve_async<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    options: SolveOptions,
+) -> Result<SolveOutcome<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    return require_solution(search(table, options, true).await?);
+}
+
+/// Counts the solutions of `table`, stopping once `limit` of them are found.
+///
+/// Unlike [`solve`], a table without solutions is not an error and yields `0`.
+pub fn count_solutions<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    limit: usize,
+) -> Result<usize, SudokuError> {
+    return count_solutions_with(table, limit, SolveOptions::default());
+}
+
+/// [`count_solutions`] with the backend, workers and strategies of
+/// `options`; `limit` replaces the limit of `options`.
+pub fn count_solutions_with<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    limit: usize,
+    options: SolveOptions,
+) -> Result<usize, SudokuError> {
+    if options.backend == Backend::DancingLinks {
+        SequenceValidator{}.ensure_valid(table)?;
+        return SudokuCover::new(table)?.count(Option::Some(limit));
+    }
+    let options = SolveOptions{ limit: Option::Some(limit), ..options };
+    return Result::Ok(runtime().block_on(search(table, options, false))?.solution_count);
+}
+
+pub fn has_unique_solution<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+) -> Result<bool, SudokuError> {
+    return has_unique_solution_with(table, SolveOptions::default());
+}
+
+pub fn has_unique_solution_with<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    options: SolveOptions,
+) -> Result<bool, SudokuError> {
+    return Result::Ok(count_solutions_with(table, 2, options)? == 1);
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    return tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .expect("failed to start the solver runtime");
+}
+
+fn require_solution<T>(outcome: SolveOutcome<T>) -> Result<SolveOutcome<T>, SudokuError> {
+    if outcome.solution_count == 0 {
+        return Result::Err(SudokuError::SearchExhausted);
+    }
+    return Result::Ok(outcome);
+}
+
+/// Runs the configured backend; an empty outcome means there is no solution.
+/// Solved tables are only kept when `collect` is set, the count is always right.
+async fn search<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    options: SolveOptions,
+    collect: bool,
+) -> Result<SolveOutcome<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    if options.backend == Backend::DancingLinks {
+        return search_exact_cover(table, options.limit);
+    }
+    let started = Instant::now();
+    SequenceValidator{}.ensure_valid(table)?;
+    let mut handles = vec![];
+    let table_bus = TableBus::<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>::new();
+    let solution_bus = TableBus::<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>::new();
+    let nodes = Arc::new(AtomicUsize::new(0));
+    let branches = Arc::new(AtomicUsize::new(0));
+    let found = Arc::new(AtomicUsize::new(0));
+    let root = match options.fill {
+        FillStrategy::Propagation => PropagationFiller{}.propagate(table.clone())?,
+        FillStrategy::Reduction => ReductionFiller{}.reduce(table.clone())?,
+        FillStrategy::Simple => Option::Some(table.clone()),
+    };
+    if let Some(root) = root {
+        table_bus.insert(root);
+    }
+    for _ in 0..options.threads.max(1) {
+        let worker = Worker {
+            selection: options.selection,
+            fill: options.fill,
+            limit: options.limit,
+            collect: collect,
+            table_bus: table_bus.clone(),
+            solution_bus: solution_bus.clone(),
+            nodes: nodes.clone(),
+            branches: branches.clone(),
+            found: found.clone(),
+        };
+        handles.push(tokio::spawn(worker.run()));
+    }
+
+    for handle in handles {
+        handle.await.expect("solver worker panicked")?;
+    }
+    let found = found.load(Ordering::SeqCst);
+    return Result::Ok(SolveOutcome {
+        solution_count: options.limit.map_or(found, |limit| found.min(limit)),
+        solutions: solution_bus.drain(),
+        stats: SolveStats {
+            nodes: nodes.load(Ordering::Relaxed),
+            branches: branches.load(Ordering::Relaxed),
+            elapsed: started.elapsed(),
+        },
+    });
+}
+
+fn search_exact_cover<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+    table: &Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    limit: Option<usize>,
+) -> Result<SolveOutcome<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>, SudokuError> {
+    let started = Instant::now();
+    SequenceValidator{}.ensure_valid(table)?;
+    let search = SudokuCover::new(table)?.search(limit, true)?;
+    return Result::Ok(SolveOutcome {
+        solutions: search.solutions,
+        solution_count: search.solution_count,
+        stats: SolveStats {
+            nodes: search.nodes,
+            branches: 0,
+            elapsed: started.elapsed(),
+        },
+    });
+}
+
+struct Worker<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> {
+    selection: SelectionStrategy,
+    fill: FillStrategy,
+    limit: Option<usize>,
+    collect: bool,
+    table_bus: TableBus<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>,
+    solution_bus: TableBus<Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>>,
+    nodes: Arc<AtomicUsize>,
+    branches: Arc<AtomicUsize>,
+    /// Solutions found by all workers, including the ones past the limit.
+    found: Arc<AtomicUsize>,
+}
+
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> Worker<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    async fn run(self) -> Result<(), SudokuError> {
+        while let Some(table) = self.table_bus.receive().await {
+            let processed = self.process(table);
+            self.table_bus.done();
+            if processed.is_err() {
+                self.table_bus.close();
+                return processed;
+            }
+        }
+        return Result::Ok(());
+    }
+
+    /// Either stores `table` as a solution or pushes its children back to the bus.
+    fn process(&self, table: Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>) -> Result<(), SudokuError> {
+        let validator = SequenceValidator{};
+        self.nodes.fetch_add(1, Ordering::Relaxed);
+        if table.is_filled() {
+            if validator.validate(&table)?.is_valid() {
+                let found = self.found.fetch_add(1, Ordering::SeqCst) + 1;
+                if self.collect && self.limit.is_none_or(|limit| found <= limit) {
+                    self.solution_bus.insert(table);
+                }
+                if self.limit.is_some_and(|limit| found >= limit) {
+                    self.table_bus.close();
+                }
+            }
+            return Result::Ok(());
+        }
+        let next_empty_point = match self.selection.select(&table)? {
+            Some(point) => point,
+            None => return Result::Ok(()),
+        };
+        if let Some(table_vec) = self.fill.fill(&table, &next_empty_point)? {
+            self.branches.fetch_add(table_vec.len(), Ordering::Relaxed);
+            for t in table_vec {
+                self.table_bus.insert(t)
+            }
+        }
+        return Result::Ok(());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table4, Table6, Table16};
+    use crate::point_selection::empty_point_search::SearchDirection;
+
+    #[test]
+    fn test_solve_returns_single_solution() {
+        let t = Table::new_from([
+            [0,0,9,2,1,8,0,0,0],
+            [1,7,0,0,9,6,8,0,0],
+            [0,4,0,0,5,0,0,0,6],
+            [4,5,1,0,6,0,3,7,0],
+            [0,0,0,0,0,5,0,0,9],
+            [9,0,2,3,7,0,5,0,0],
+            [6,0,0,5,0,1,0,0,0],
+            [0,0,0,0,4,9,2,5,7],
+            [0,9,4,8,0,0,0,1,3]
+        ]);
+        let outcome = solve(&t, SolveOptions{ threads: 4, ..SolveOptions::default() }).unwrap();
+        assert_eq!(outcome.solution_count, 1);
+        assert!(outcome.solutions[0].is_filled());
+        assert!(outcome.stats.nodes > 0);
+
+        let directional = SolveOptions{
+            threads: 4,
+            selection: SelectionStrategy::Directional(SearchDirection::BottomRightTop),
+            fill: FillStrategy::Simple,
+            ..SolveOptions::default()
+        };
+        let outcome_directional = solve(&t, directional).unwrap();
+        assert_eq!(outcome_directional.solution_count, 1);
+        assert_eq!(format!("{:?}", outcome_directional.solutions[0]), format!("{:?}", outcome.solutions[0]));
+    }
+
+    #[test]
+    fn test_propagation_only_queues_branching_tables() {
+        let t = Table::new_from([
+            [0,0,0,0,0,0,0,1,2],
+            [0,0,0,0,3,5,0,0,0],
+            [0,0,0,6,0,0,0,7,0],
+            [7,0,0,0,0,0,3,0,0],
+            [0,0,0,4,0,0,8,0,0],
+            [1,0,0,0,0,0,0,0,0],
+            [0,0,0,1,2,0,0,0,0],
+            [0,8,0,0,0,0,0,4,0],
+            [0,5,0,0,0,0,6,0,0]
+        ]);
+        let simple = solve(&t, SolveOptions{ threads: 4, fill: FillStrategy::Simple, ..SolveOptions::default() }).unwrap();
+        let propagated = solve(&t, SolveOptions{ threads: 4, ..SolveOptions::default() }).unwrap();
+        assert_eq!(simple.solution_count, 1);
+        assert_eq!(propagated.solution_count, 1);
+        assert_eq!(format!("{:?}", simple.solutions[0]), format!("{:?}", propagated.solutions[0]));
+        assert!(propagated.stats.nodes < simple.stats.nodes);
+    }
+
+    #[test]
+    fn test_dancing_links_backend_matches_search() {
+        let t = Table6::from_rows([
+            [0,0,3,0,1,0],
+            [5,6,0,3,2,0],
+            [0,5,4,2,0,3],
+            [2,0,6,4,5,0],
+            [0,1,2,0,4,5],
+            [0,4,0,1,0,0],
+        ]);
+        let search = solve(&t, SolveOptions{ threads: 2, ..SolveOptions::default() }).unwrap();
+        let cover = solve(&t, SolveOptions{ backend: Backend::DancingLinks, ..SolveOptions::default() }).unwrap();
+        assert_eq!(cover.solution_count, 1);
+        assert!(cover.stats.nodes > 0);
+        assert_eq!(format!("{:?}", cover.solutions[0]), format!("{:?}", search.solutions[0]));
+
+        let mut fields = [[0u8; 9]; 9];
+        fields[0][0] = 5;
+        fields[0][8] = 5;
+        let error = solve(&Table::new_from(fields), SolveOptions{ backend: Backend::DancingLinks, ..SolveOptions::default() });
+        assert!(matches!(error, Result::Err(SudokuError::Conflict(_))));
+    }
+
+    #[test]
+    fn test_limit_stops_search() {
+        let outcome = solve(&Table::new(), SolveOptions{ limit: Option::Some(3), ..SolveOptions::default() }).unwrap();
+        assert_eq!(outcome.solution_count, 3);
+        let cover = solve(&Table::new(), SolveOptions{ limit: Option::Some(3), backend: Backend::DancingLinks, ..SolveOptions::default() }).unwrap();
+        assert_eq!(cover.solution_count, 3);
+    }
+
+    #[test]
+    fn test_count_solutions() {
+        assert_eq!(count_solutions(&Table::new(), 2).unwrap(), 2);
+        assert_eq!(count_solutions(&Table16::empty(), 2).unwrap(), 2);
+        let unsolvable = Table4::from_rows([
+            [1,2,0,0],
+            [0,0,0,0],
+            [0,0,3,0],
+            [0,0,4,0],
+        ]);
+        assert_eq!(count_solutions(&unsolvable, 2).unwrap(), 0);
+        let mut fields = [[0u8; 9]; 9];
+        fields[0][0] = 5;
+        fields[0][8] = 5;
+        assert!(count_solutions(&Table::new_from(fields), 2).is_err());
+    }
+
+    #[test]
+    fn test_has_unique_solution() {
+        let t = Table::new_from([
+            [0,0,9,2,1,8,0,0,0],
+            [1,7,0,0,9,6,8,0,0],
+            [0,4,0,0,5,0,0,0,6],
+            [4,5,1,0,6,0,3,7,0],
+            [0,0,0,0,0,5,0,0,9],
+            [9,0,2,3,7,0,5,0,0],
+            [6,0,0,5,0,1,0,0,0],
+            [0,0,0,0,4,9,2,5,7],
+            [0,9,4,8,0,0,0,1,3]
+        ]);
+        assert!(has_unique_solution(&t).unwrap());
+        assert!(!has_unique_solution(&Table6::empty()).unwrap());
+
+        let dlx = SolveOptions{ backend: Backend::DancingLinks, ..SolveOptions::default() };
+        assert!(has_unique_solution_with(&t, dlx.clone()).unwrap());
+        assert!(!has_unique_solution_with(&Table6::empty(), dlx.clone()).unwrap());
+        assert_eq!(count_solutions_with(&Table6::empty(), 7, dlx).unwrap(), 7);
+        let single = SolveOptions{ threads: 1, limit: Option::Some(1), ..SolveOptions::default() };
+        assert_eq!(count_solutions_with(&Table6::empty(), 3, single).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_solve_rejects_conflicting_givens() {
+        let mut fields = [[0u8; 9]; 9];
+        fields[0][0] = 5;
+        fields[0][8] = 5;
+        match solve(&Table::new_from(fields), SolveOptions::default()) {
+            Result::Err(SudokuError::Conflict(conflicts)) => assert_eq!(conflicts.len(), 1),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_solve_reports_exhausted_search() {
+        let t = Table4::from_rows([
+            [1,2,0,0],
+            [0,0,0,0],
+            [0,0,3,0],
+            [0,0,4,0],
+        ]);
+        let error = solve(&t, SolveOptions::default()).unwrap_err();
+        assert_eq!(error, SudokuError::SearchExhausted);
+    }
+
+    #[test]
+    fn test_solve_rectangular_zones() {
+        let t = Table6::from_rows([
+            [0,0,3,0,1,0],
+            [5,6,0,3,2,0],
+            [0,5,4,2,0,3],
+            [2,0,6,4,5,0],
+            [0,1,2,0,4,5],
+            [0,4,0,1,0,0],
+        ]);
+        let outcome = solve(&t, SolveOptions{ threads: 2, ..SolveOptions::default() }).unwrap();
+        assert_eq!(outcome.solution_count, 1);
+        assert!(outcome.solutions[0].is_filled());
+    }
+}
@@ -1,31 +1,90 @@
 use crate::point::Point;
 use crate::point::Axis;
-use crate::point::CoordinateError;
+use crate::error::SudokuError;
+use crate::candidates::{mask_values, CandidateGrid, CandidateMask};
 use std::fmt;
 
 pub const TABLE_SIZE: usize = 9;
+pub const ZONE_SIZE: usize = 3;
 
+/// A sudoku board of `SIZE` x `SIZE` fields split into zones of
+/// `ZONE_HEIGHT` rows by `ZONE_WIDTH` columns. Empty fields hold `0`.
+///
+/// The defaults describe the classic 9x9 board with 3x3 zones.
 #[derive(Clone)]
-pub struct Table {
-    fields: [[u8; TABLE_SIZE]; TABLE_SIZE]
+pub struct Table<const SIZE: usize = TABLE_SIZE, const ZONE_HEIGHT: usize = ZONE_SIZE, const ZONE_WIDTH: usize = ZONE_SIZE> {
+    fields: [[u8; SIZE]; SIZE],
+    candidates: CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
 }
 
+pub type Table4 = Table<4, 2, 2>;
+pub type Table6 = Table<6, 2, 3>;
+pub type Table12 = Table<12, 3, 4>;
+pub type Table16 = Table<16, 4, 4>;
+pub type Table25 = Table<25, 5, 5>;
+
 impl Table {
     pub fn new() -> Table {
-        return Table {
-            fields: [[0u8; TABLE_SIZE]; TABLE_SIZE]
-        }
+        return Table::empty();
     }
 
     pub fn new_from(fields: [[u8; TABLE_SIZE]; TABLE_SIZE]) -> Table {
+        return Table::from_rows(fields);
+    }
+}
+
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    const LAYOUT_CHECK: () = assert!(
+        SIZE == ZONE_HEIGHT * ZONE_WIDTH && SIZE > 0 && SIZE < u8::MAX as usize,
+        "zones must tile the table exactly"
+    );
+
+    pub fn empty() -> Self {
+        return Self::from_rows([[0u8; SIZE]; SIZE]);
+    }
+
+    /// Builds a table from rows of values, `fields[y][x]`.
+    pub fn from_rows(fields: [[u8; SIZE]; SIZE]) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::LAYOUT_CHECK;
         return Table {
-            fields: fields
+            candidates: CandidateGrid::from_fields(&fields),
+            fields: fields,
         }
     }
 
+    /// Builds a table from rows of values and the candidates of `grid`,
+    /// such as a grid read back with serde. A candidate the values rule out
+    /// is reported as [`SudokuError::InvalidDigit`]; values the grid lacks
+    /// stay eliminated.
+    pub fn with_candidates(
+        fields: [[u8; SIZE]; SIZE],
+        grid: &CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+    ) -> Result<Self, SudokuError> {
+        let mut table = Self::from_rows(fields);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                let point = Point{x: x, y: y};
+                let allowed = table.candidates.candidates(&point);
+                let kept = grid.candidates(&point);
+                if let Some(value) = mask_values(kept & !allowed).first() {
+                    return Result::Err(SudokuError::InvalidDigit { point: point, value: *value });
+                }
+                for value in mask_values(allowed & !kept) {
+                    table.candidates.eliminate(&point, value);
+                }
+            }
+        }
+        return Result::Ok(table);
+    }
+
+    pub fn candidate_grid(&self) -> &CandidateGrid<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+        return &self.candidates;
+    }
+
     pub fn is_filled(&self) -> bool {
-        for x in 0..TABLE_SIZE {
-            for y in 0..TABLE_SIZE {
+        for x in 0..SIZE {
+            for y in 0..SIZE {
                 if self.fields[x][y] == 0 {
                     return false;
                 }
@@ -34,105 +93,147 @@ impl Table {
         return true;
     }
 
-    fn check_range(point: &Point) -> Result<(), CoordinateError> {
-        if point.x > 8 {
-            return Result::Err(CoordinateError::OutOfRange(Axis::X))
+    fn check_range(point: &Point) -> Result<(), SudokuError> {
+        if point.x >= SIZE {
+            return Result::Err(SudokuError::OutOfRange(Axis::X))
         }
-        if point.y > 8 {
-            return Result::Err(CoordinateError::OutOfRange(Axis::Y))
+        if point.y >= SIZE {
+            return Result::Err(SudokuError::OutOfRange(Axis::Y))
         }
         return Result::Ok(());
     }
 }
 
-impl crate::SquareTable for Table {
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> Default for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn default() -> Self {
+        return Self::empty();
+    }
+}
+
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> crate::SquareTable for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
     fn dimensions(&self) -> usize {
-        return TABLE_SIZE;
+        return SIZE;
     }
 }
 
-impl crate::SudokuTable for Table {
-    fn points_in_zone(&self, point: &Point) -> Result<Vec<Point>, CoordinateError> {
-        match Table::check_range(&point) {
-            Result::Err(x) => return Result::Err(x),
-            _ => (),
-        }
-        let x_offset = (point.x / 3) * 3;
-        let y_offset = (point.y / 3) * 3;
-        let mut points = Vec::with_capacity(9);
-        for x in 0..3 {
-            for y in 0..3 {
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> crate::SudokuTable for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn points_in_zone(&self, point: &Point) -> Result<Vec<Point>, SudokuError> {
+        Self::check_range(point)?;
+        let x_offset = (point.x / ZONE_WIDTH) * ZONE_WIDTH;
+        let y_offset = (point.y / ZONE_HEIGHT) * ZONE_HEIGHT;
+        let mut points = Vec::with_capacity(SIZE);
+        for x in 0..ZONE_WIDTH {
+            for y in 0..ZONE_HEIGHT {
                 points.push(Point{x: x_offset + x, y: y_offset + y})
             }
         }
         return Result::Ok(points);
     }
 
+    fn zone_index(&self, point: &Point) -> Result<usize, SudokuError> {
+        Self::check_range(point)?;
+        return Result::Ok(CandidateGrid::<SIZE, ZONE_HEIGHT, ZONE_WIDTH>::zone_index(point));
+    }
+
     fn possible_values(&self) -> Vec<u8> {
-        let mut possible_values = Vec::with_capacity(9);
-        for i in 1..(TABLE_SIZE + 1) {
+        let mut possible_values = Vec::with_capacity(SIZE);
+        for i in 1..(SIZE + 1) {
             possible_values.push(i as u8);
         }
         return possible_values;
     }
 }
 
-impl fmt::Debug for Table {
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> fmt::Debug for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut row: &[u8; 9];
-        writeln!(f, "┌───────┬───────┬───────┐").ok();
+        let width = SIZE.to_string().len();
+        let border = |left: &str, middle: &str, right: &str| -> String {
+            let segment = "─".repeat(ZONE_WIDTH * (width + 1) + 1);
+            let segments = vec![segment; SIZE / ZONE_WIDTH];
+            return format!("{}{}{}", left, segments.join(middle), right);
+        };
         let val = |v: u8| -> String {
             if v == 0 {
-                return String::from(" ");
+                return " ".repeat(width);
             }
-            return v.to_string();
+            return format!("{:>width$}", v, width = width);
         };
-        for i in 0..9 {
-            row = &self.fields[i];
-            writeln!(
-                f,
-                "│ {} {} {} │ {} {} {} │ {} {} {} │",
-                val(row[0]), val(row[1]), val(row[2]), val(row[3]), val(row[4]), val(row[5]), val(row[6]), val(row[7]), val(row[8])
-            ).ok();
-            if (i + 1) % 3 == 0 && i != 8 {
-                writeln!(f, "├───────┼───────┼───────┤").ok();
+        writeln!(f, "{}", border("┌", "┬", "┐"))?;
+        for (i, row) in self.fields.iter().enumerate() {
+            let mut line = String::from("│");
+            for zone in row.chunks(ZONE_WIDTH) {
+                for v in zone {
+                    line.push(' ');
+                    line.push_str(&val(*v));
+                }
+                line.push_str(" │");
+            }
+            writeln!(f, "{}", line)?;
+            if (i + 1) % ZONE_HEIGHT == 0 && i != SIZE - 1 {
+                writeln!(f, "{}", border("├", "┼", "┤"))?;
             }
         }
-        writeln!(f, "└───────┴───────┴───────┘").ok();
+        writeln!(f, "{}", border("└", "┴", "┘"))?;
         return Result::Ok(());
     }
 }
 
-impl super::Settable for Table {
-    fn set_in_point(&self, point: &Point, value: u8) -> Result<Box<Self>, CoordinateError> {
-        match Table::check_range(&point) {
-            Result::Err(x) => return Result::Err(x),
-            _ => (),
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> super::Settable for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn set_in_point(&self, point: &Point, value: u8) -> Result<Box<Self>, SudokuError> {
+        Self::check_range(point)?;
+        if value as usize > SIZE {
+            return Result::Err(SudokuError::InvalidDigit { point: point.clone(), value: value });
         }
 
-        let mut fields = self.fields.clone();
-        fields[point.y][point.x] = value;
-        return Result::Ok(Box::from(Table{fields: fields}));
+        let mut table = self.clone();
+        let previous = table.fields[point.y][point.x];
+        if previous == value {
+            return Result::Ok(Box::from(table));
+        }
+        if previous != 0 {
+            table.fields[point.y][point.x] = 0;
+            table.candidates.remove(&table.fields, point, previous);
+        }
+        if value != 0 {
+            table.fields[point.y][point.x] = value;
+            table.candidates.place(point, value);
+        }
+        return Result::Ok(Box::from(table));
     }
 }
 
-impl super::Selectable for Table {
-    fn value_in_point(&self, point: &Point) -> Result<u8, CoordinateError> {
-        match Table::check_range(&point) {
-            Result::Err(x) => return Result::Err(x),
-            _ => (),
-        }
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> super::Selectable for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn value_in_point(&self, point: &Point) -> Result<u8, SudokuError> {
+        Self::check_range(point)?;
         return Result::Ok(self.fields[point.y][point.x]);
     }
 }
 
+impl<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize> super::Candidates for Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+    fn candidates_in_point(&self, point: &Point) -> Result<CandidateMask, SudokuError> {
+        Self::check_range(point)?;
+        return Result::Ok(self.candidates.candidates(point));
+    }
+
+    fn eliminate_in_point(&self, point: &Point, value: u8) -> Result<Box<Self>, SudokuError> {
+        Self::check_range(point)?;
+        if value == 0 || value as usize > SIZE {
+            return Result::Err(SudokuError::InvalidDigit { point: point.clone(), value: value });
+        }
+        let mut table = self.clone();
+        table.candidates.eliminate(point, value);
+        return Result::Ok(Box::from(table));
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Table;
+    use super::{Table, Table4, Table6, Table16};
+    use crate::candidates::mask_values;
     use crate::point::Point;
     use crate::*;
     use crate::point::Axis;
-    use crate::point::CoordinateError;
+    use crate::error::SudokuError;
     #[test]
     fn test_accessing_value() {
         let t = Table::new();
@@ -153,7 +254,7 @@ mod test {
         let t = Table::new();
 
         let x_error = t.value_in_point(&Point{ x: 9, y: 5 }).unwrap_err();
-        assert_eq!(x_error, CoordinateError::OutOfRange(Axis::X));
+        assert_eq!(x_error, SudokuError::OutOfRange(Axis::X));
     }
 
     #[test]
@@ -161,7 +262,7 @@ mod test {
         let t = Table::new();
 
         let y_error = t.value_in_point(&Point{ x: 1, y: 9 }).unwrap_err();
-        assert_eq!(y_error, CoordinateError::OutOfRange(Axis::Y));
+        assert_eq!(y_error, SudokuError::OutOfRange(Axis::Y));
     }
 
     #[test]
@@ -183,6 +284,17 @@ mod test {
         println!("{:?}", new_table_2);
     }
 
+    #[test]
+    fn test_set_invalid_digit() {
+        let t = Table::new();
+        let p = Point{ x: 2, y: 4 };
+
+        let error = t.set_in_point(&p, 42).unwrap_err();
+        assert_eq!(error, SudokuError::InvalidDigit { point: p.clone(), value: 42 });
+        assert!(Table4::empty().set_in_point(&p, 5).is_err());
+        assert!(Table4::empty().set_in_point(&Point{ x: 1, y: 1 }, 4).is_ok());
+    }
+
     #[test]
     fn test_debug_print() {
         let t = Table::new();
@@ -218,7 +330,7 @@ mod test {
             [4,0,0,1,8,0,0,6,7],
             [5,0,0,0,0,4,8,2,9],
         ]);
-        assert_eq!(unfilled.is_filled(), false);
+        assert!(!unfilled.is_filled());
         let filled = Table::new_from([
             [1,8,1,5,3,1,2,7,6],
             [1,5,1,6,1,1,1,1,1],
@@ -230,7 +342,7 @@ mod test {
             [4,1,1,1,8,1,1,6,7],
             [5,1,1,1,1,4,8,2,9],
         ]);
-        assert_eq!(filled.is_filled(), true);
+        assert!(filled.is_filled());
     }
 
     #[test]
@@ -260,4 +372,97 @@ mod test {
         assert_eq!(points_in_zone[7], Point{x: 8, y: 4});
         assert_eq!(points_in_zone[8], Point{x: 8, y: 5});
     }
+
+    #[test]
+    fn test_debug_print_matches_classic_layout() {
+        let t = Table::new_from([
+            [0,0,9,2,1,8,0,0,0],
+            [1,7,0,0,9,6,8,0,0],
+            [0,4,0,0,5,0,0,0,6],
+            [4,5,1,0,6,0,3,7,0],
+            [0,0,0,0,0,5,0,0,9],
+            [9,0,2,3,7,0,5,0,0],
+            [6,0,0,5,0,1,0,0,0],
+            [0,0,0,0,4,9,2,5,7],
+            [0,9,4,8,0,0,0,1,3]
+        ]);
+        let printed = format!("{:?}", t);
+        let lines: Vec<&str> = printed.lines().collect();
+        assert_eq!(lines.len(), 13);
+        assert_eq!(lines[0], "┌───────┬───────┬───────┐");
+        assert_eq!(lines[1], "│     9 │ 2 1 8 │       │");
+        assert_eq!(lines[4], "├───────┼───────┼───────┤");
+        assert_eq!(lines[12], "└───────┴───────┴───────┘");
+    }
+
+    #[test]
+    fn test_rectangular_zones() {
+        let t = Table6::empty();
+        assert_eq!(t.dimensions(), 6);
+        assert_eq!(t.possible_values(), vec![1, 2, 3, 4, 5, 6]);
+        let points_in_zone = t.points_in_zone(&Point{x: 4, y: 3}).unwrap();
+        assert_eq!(points_in_zone.len(), 6);
+        assert_eq!(points_in_zone[0], Point{x: 3, y: 2});
+        assert_eq!(points_in_zone[1], Point{x: 3, y: 3});
+        assert_eq!(points_in_zone[5], Point{x: 5, y: 3});
+        assert_eq!(t.zone_index(&Point{x: 4, y: 3}).unwrap(), 3);
+        assert_eq!(t.zone_index(&Point{x: 2, y: 5}).unwrap(), 4);
+        let x_error = t.value_in_point(&Point{ x: 6, y: 0 }).unwrap_err();
+        assert_eq!(x_error, SudokuError::OutOfRange(Axis::X));
+    }
+
+    #[test]
+    fn test_debug_print_wide_values() {
+        let t = *Table16::empty().set_in_point(&Point{x: 0, y: 0}, 16).unwrap();
+        let printed = format!("{:?}", t);
+        let lines: Vec<&str> = printed.lines().collect();
+        assert_eq!(lines.len(), 21);
+        assert!(lines[1].starts_with("│ 16    "));
+    }
+
+    #[test]
+    fn test_candidates_follow_placements() {
+        let t = Table::new();
+        let p = Point{ x: 4, y: 4 };
+        assert_eq!(mask_values(t.candidates_in_point(&p).unwrap()), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+        let t = *t.set_in_point(&Point{ x: 4, y: 0 }, 3).unwrap();
+        let t = *t.set_in_point(&Point{ x: 0, y: 4 }, 5).unwrap();
+        let t = *t.set_in_point(&Point{ x: 3, y: 5 }, 7).unwrap();
+        assert_eq!(mask_values(t.candidates_in_point(&p).unwrap()), vec![1, 2, 4, 6, 8, 9]);
+
+        let cleared = *t.set_in_point(&Point{ x: 4, y: 0 }, 0).unwrap();
+        assert_eq!(mask_values(cleared.candidates_in_point(&p).unwrap()), vec![1, 2, 3, 4, 6, 8, 9]);
+
+        let eliminated = *t.eliminate_in_point(&p, 9).unwrap();
+        assert_eq!(mask_values(eliminated.candidates_in_point(&p).unwrap()), vec![1, 2, 4, 6, 8]);
+        assert!(t.eliminate_in_point(&p, 10).is_err());
+    }
+
+    #[test]
+    fn test_clearing_keeps_eliminations() {
+        let p = Point{ x: 4, y: 4 };
+        let t = *Table::new().set_in_point(&Point{ x: 4, y: 0 }, 3).unwrap();
+        let t = *t.eliminate_in_point(&p, 9).unwrap();
+        let cleared = *t.set_in_point(&Point{ x: 4, y: 0 }, 0).unwrap();
+        assert_eq!(mask_values(cleared.candidates_in_point(&p).unwrap()), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        let replaced = *t.set_in_point(&Point{ x: 4, y: 0 }, 5).unwrap();
+        assert_eq!(mask_values(replaced.candidates_in_point(&p).unwrap()), vec![1, 2, 3, 4, 6, 7, 8]);
+        assert_eq!(mask_values(replaced.candidates_in_point(&Point{ x: 4, y: 0 }).unwrap()), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_with_candidates() {
+        let t = *Table4::empty().set_in_point(&Point{ x: 0, y: 0 }, 1).unwrap();
+        let p = Point{ x: 3, y: 3 };
+        let eliminated = *t.eliminate_in_point(&p, 2).unwrap();
+        let fields = [[1, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]];
+        let restored = Table4::with_candidates(fields, eliminated.candidate_grid()).unwrap();
+        assert!(restored.candidate_grid() == eliminated.candidate_grid());
+        assert_eq!(mask_values(restored.candidates_in_point(&p).unwrap()), vec![1, 3, 4]);
+
+        let cleared = Table4::empty();
+        let error = Table4::with_candidates(fields, cleared.candidate_grid()).unwrap_err();
+        assert_eq!(error, SudokuError::InvalidDigit { point: Point{ x: 0, y: 0 }, value: 1 });
+    }
 }
@@ -0,0 +1,283 @@
+use super::{cell_name, combinations, describe_eliminations, list, Step, StepFinder, Technique};
+use crate::{Candidates, Selectable, SudokuTable, SquareTable};
+use crate::candidates::mask_of;
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::point_selection::line_point_selection::{LinePointSelection, SelectionType};
+
+/// Finds `size` rows whose places for a value all lie in the same `size`
+/// columns, or the other way round. Each base line needs the value in one
+/// of the cover lines, so the value fills the cover lines and is removed
+/// from the rest of them.
+pub struct Fish {
+    pub size: usize,
+}
+
+impl Fish {
+    fn technique(&self) -> Technique {
+        return match self.size {
+            2 => Technique::XWing,
+            3 => Technique::Swordfish,
+            _ => Technique::Jellyfish,
+        };
+    }
+}
+
+impl StepFinder for Fish {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        return find_fish(table, self.size, self.technique(), Option::None);
+    }
+}
+
+/// A [`Fish`] with extra places, the fins, all in one zone. Either a fin or
+/// the fish holds the value, so only cover points sharing the zone of the
+/// fins lose it. The fish is sashimi when a base line keeps a single place
+/// in the cover lines without its fins. Fins fit in the width of a zone, so
+/// base lines with more places than the fish and one zone are skipped.
+pub struct FinnedFish {
+    pub size: usize,
+    pub sashimi: bool,
+}
+
+impl FinnedFish {
+    fn technique(&self) -> Technique {
+        return match (self.size, self.sashimi) {
+            (2, false) => Technique::FinnedXWing,
+            (2, true) => Technique::SashimiXWing,
+            (3, false) => Technique::FinnedSwordfish,
+            (3, true) => Technique::SashimiSwordfish,
+            (_, false) => Technique::FinnedJellyfish,
+            (_, true) => Technique::SashimiJellyfish,
+        };
+    }
+}
+
+impl StepFinder for FinnedFish {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        return find_fish(table, self.size, self.technique(), Option::Some(self.sashimi));
+    }
+}
+
+/// Where a value can go in one base line.
+#[derive(Clone)]
+struct BaseLine {
+    index: usize,
+    places: Vec<Point>,
+}
+
+/// Looks for a basic fish, or a finned one when `sashimi` is given.
+fn find_fish<T>(table: &T, size: usize, technique: Technique, sashimi: Option<bool>) -> Result<Option<Step>, SudokuError>
+    where T: Selectable + SudokuTable + SquareTable + Candidates {
+    for value in table.possible_values() {
+        for horizontal in [true, false] {
+            // Rows as base lines are crossed by columns and the other way round.
+            let cover_of = |point: &Point| if horizontal { point.x } else { point.y };
+            let base_of = |point: &Point| if horizontal { point.y } else { point.x };
+            // Fins share a zone, so they span at most its extent along a base line.
+            let fin_room = table.points_in_zone(&Point{x: 0, y: 0})?.iter().filter(|point| base_of(point) == 0).count();
+
+            let mut lines = Vec::new();
+            for index in 0..table.dimensions() {
+                let mut places = Vec::new();
+                for point in line_points(table, horizontal, index)? {
+                    if table.candidates_in_point(&point)? & mask_of(value) != 0 {
+                        places.push(point);
+                    }
+                }
+                let usable = match sashimi {
+                    None => places.len() >= 2 && places.len() <= size,
+                    Some(_) => !places.is_empty() && places.len() <= size + fin_room,
+                };
+                if usable {
+                    lines.push(BaseLine { index: index, places: places });
+                }
+            }
+
+            for base in combinations(&lines, size) {
+                let mut union: Vec<usize> = base.iter().flat_map(|line| line.places.iter().map(cover_of)).collect();
+                union.sort();
+                union.dedup();
+                let cover_sets = match sashimi {
+                    None if union.len() == size => vec![union],
+                    None => continue,
+                    Some(_) if union.len() <= size || union.len() > size + fin_room => continue,
+                    Some(_) => combinations(&union, size),
+                };
+                for covers in cover_sets {
+                    let mut fins: Vec<Point> = Vec::new();
+                    let mut fin_zone = Option::None;
+                    let mut scattered = false;
+                    for point in base.iter().flat_map(|line| line.places.iter()).filter(|point| !covers.contains(&cover_of(point))) {
+                        let zone = table.zone_index(point)?;
+                        if fin_zone.is_some_and(|fin_zone| fin_zone != zone) {
+                            scattered = true;
+                            break;
+                        }
+                        fin_zone = Option::Some(zone);
+                        fins.push(point.clone());
+                    }
+                    if scattered {
+                        continue;
+                    }
+                    if let (Some(is_sashimi), Some(_)) = (sashimi, fin_zone) {
+                        let covered: Vec<usize> = base.iter()
+                            .map(|line| line.places.iter().filter(|point| covers.contains(&cover_of(point))).count())
+                            .collect();
+                        if covered.contains(&0) || covered.contains(&1) != is_sashimi {
+                            continue;
+                        }
+                    }
+
+                    let mut eliminations = Vec::new();
+                    for cover in covers.iter() {
+                        for point in line_points(table, !horizontal, *cover)? {
+                            if base.iter().any(|line| line.index == base_of(&point)) {
+                                continue;
+                            }
+                            if let Some(zone) = fin_zone {
+                                if table.zone_index(&point)? != zone {
+                                    continue;
+                                }
+                            }
+                            if table.candidates_in_point(&point)? & mask_of(value) != 0 {
+                                eliminations.push((point, value));
+                            }
+                        }
+                    }
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    let base_names = lines_name(horizontal, base.iter().map(|line| line.index).collect());
+                    let cover_names = lines_name(!horizontal, covers.clone());
+                    let mut explanation = format!(
+                        "{} on {}: {} (base) only hold {} in {} (cover)",
+                        technique, value, base_names, value, cover_names
+                    );
+                    if !fins.is_empty() {
+                        let fin_names: Vec<String> = fins.iter().map(cell_name).collect();
+                        explanation.push_str(&format!(
+                            " apart from the fin{} {}, so either the fish or a fin holds {} and cover points sharing a zone with the fins lose it",
+                            if fins.len() > 1 { "s" } else { "" }, list(&fin_names), value
+                        ));
+                    } else {
+                        explanation.push_str(&format!(", so the rest of the cover loses {}", value));
+                    }
+                    explanation.push_str(&format!(": {}.", describe_eliminations(&eliminations)));
+                    return Result::Ok(Option::Some(Step {
+                        technique: technique,
+                        explanation: explanation,
+                        cells: base.iter().flat_map(|line| line.places.iter().cloned()).collect(),
+                        candidates: vec![value],
+                        placements: Vec::new(),
+                        eliminations: eliminations,
+                    }));
+                }
+            }
+        }
+    }
+    return Result::Ok(Option::None);
+}
+
+/// Points of row `index` when `horizontal`, of column `index` otherwise.
+fn line_points(table: &impl SquareTable, horizontal: bool, index: usize) -> Result<Vec<Point>, SudokuError> {
+    let line_point_selection = LinePointSelection{};
+    return if horizontal {
+        line_point_selection.get_points(table, &Point{x: 0, y: index}, SelectionType::Horizontal)
+    } else {
+        line_point_selection.get_points(table, &Point{x: index, y: 0}, SelectionType::Vertical)
+    };
+}
+
+/// `rows 2 and 6` or `columns 1, 4 and 8`.
+fn lines_name(horizontal: bool, indices: Vec<usize>) -> String {
+    let numbers: Vec<String> = indices.iter().map(|index| (index + 1).to_string()).collect();
+    return format!("{} {}", if horizontal { "rows" } else { "columns" }, list(&numbers));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table, Table16, Table4};
+    use crate::techniques::test::with_candidates;
+
+    /// Leaves 1 in the given columns of each listed row of `table`, and
+    /// everywhere else.
+    fn with_places<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        table: Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+        rows: &[(usize, &[usize])],
+    ) -> Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+        let others: Vec<u8> = (2..=SIZE as u8).collect();
+        let mut t = table;
+        for (y, columns) in rows.iter() {
+            for x in 0..SIZE {
+                if !columns.contains(&x) {
+                    t = with_candidates(t, &Point{x: x, y: *y}, &others);
+                }
+            }
+        }
+        return t;
+    }
+
+    #[test]
+    fn test_finds_x_wing() {
+        let t = with_places(Table::new(), &[(0, &[1, 6]), (4, &[1, 6])]);
+        let step = Fish{ size: 2 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::XWing);
+        assert_eq!(step.cells, vec![Point{x: 1, y: 0}, Point{x: 6, y: 0}, Point{x: 1, y: 4}, Point{x: 6, y: 4}]);
+        assert_eq!(step.eliminations.len(), 14);
+        assert!(step.eliminations.iter().all(|(point, _)| (point.x == 1 || point.x == 6) && point.y != 0 && point.y != 4));
+        assert!(step.explanation.starts_with("X-Wing on 1: rows 1 and 5 (base) only hold 1 in columns 2 and 7 (cover)"));
+        assert!(FinnedFish{ size: 2, sashimi: false }.find(&t).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_finds_x_wing_on_small_table() {
+        let t = with_places(Table4::empty(), &[(0, &[1, 3]), (2, &[1, 3])]);
+        let step = Fish{ size: 2 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.cells, vec![Point{x: 1, y: 0}, Point{x: 3, y: 0}, Point{x: 1, y: 2}, Point{x: 3, y: 2}]);
+        assert_eq!(
+            step.eliminations,
+            vec![(Point{x: 1, y: 1}, 1), (Point{x: 1, y: 3}, 1), (Point{x: 3, y: 1}, 1), (Point{x: 3, y: 3}, 1)]
+        );
+        assert!(step.explanation.starts_with("X-Wing on 1: rows 1 and 3 (base) only hold 1 in columns 2 and 4 (cover)"));
+    }
+
+    #[test]
+    fn test_skips_lines_too_wide_for_fins() {
+        // Every line of an empty table holds more places than a fish and its fins.
+        assert!(FinnedFish{ size: 4, sashimi: false }.find(&Table16::empty()).unwrap().is_none());
+        assert!(FinnedFish{ size: 2, sashimi: true }.find(&Table::new()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_finds_swordfish_and_jellyfish() {
+        let t = with_places(Table::new(), &[(0, &[1, 6]), (4, &[1, 8]), (8, &[6, 8])]);
+        assert!(Fish{ size: 2 }.find(&t).unwrap().is_none());
+        let step = Fish{ size: 3 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::Swordfish);
+        assert_eq!(step.eliminations.len(), 18);
+
+        let t = with_places(Table::new(), &[(0, &[0, 3]), (2, &[3, 5]), (5, &[5, 8]), (7, &[0, 8])]);
+        assert!(Fish{ size: 3 }.find(&t).unwrap().is_none());
+        let step = Fish{ size: 4 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::Jellyfish);
+        assert!(step.explanation.contains("rows 1, 3, 6 and 8 (base) only hold 1 in columns 1, 4, 6 and 9 (cover)"));
+    }
+
+    #[test]
+    fn test_finds_finned_and_sashimi_x_wing() {
+        let t = with_places(Table::new(), &[(0, &[1, 6]), (4, &[1, 6, 7])]);
+        assert!(Fish{ size: 2 }.find(&t).unwrap().is_none());
+        assert!(FinnedFish{ size: 2, sashimi: true }.find(&t).unwrap().is_none());
+        let step = FinnedFish{ size: 2, sashimi: false }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::FinnedXWing);
+        assert_eq!(step.eliminations, vec![(Point{x: 6, y: 3}, 1), (Point{x: 6, y: 5}, 1)]);
+        assert!(step.explanation.contains("apart from the fin r5c8"));
+
+        let t = with_places(Table::new(), &[(0, &[1, 6]), (4, &[6, 7])]);
+        assert!(FinnedFish{ size: 2, sashimi: false }.find(&t).unwrap().is_none());
+        let step = FinnedFish{ size: 2, sashimi: true }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::SashimiXWing);
+        assert_eq!(step.eliminations, vec![(Point{x: 6, y: 3}, 1), (Point{x: 6, y: 5}, 1)]);
+    }
+}
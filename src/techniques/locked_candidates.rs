@@ -0,0 +1,166 @@
+use super::{cell_name, describe_eliminations, house_name, list, Step, StepFinder, Technique};
+use crate::{Candidates, Selectable, SudokuTable, SquareTable};
+use crate::candidates::mask_of;
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::point_selection::line_point_selection::{LinePointSelection, SelectionType};
+use crate::validators::House;
+use crate::validators::sequence_validator::SequenceValidator;
+
+/// Finds a value whose places in a zone all lie in one row or column.
+/// The zone needs the value there, so the rest of the line loses it.
+pub struct Pointing {}
+
+impl StepFinder for Pointing {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        let line_point_selection = LinePointSelection{};
+        for (house, points) in SequenceValidator::houses(table)? {
+            if !matches!(house, House::Zone(_)) {
+                continue;
+            }
+            for value in table.possible_values() {
+                let places = places_of(table, &points, value)?;
+                if places.len() < 2 {
+                    continue;
+                }
+                let lines = [
+                    (SelectionType::Horizontal, House::Row(places[0].y), places.iter().all(|point| point.y == places[0].y)),
+                    (SelectionType::Vertical, House::Column(places[0].x), places.iter().all(|point| point.x == places[0].x)),
+                ];
+                for (selection_type, line, aligned) in lines {
+                    if !aligned {
+                        continue;
+                    }
+                    let line_points = line_point_selection.get_points(table, &places[0], selection_type)?;
+                    let outside: Vec<Point> = line_points.into_iter().filter(|point| !points.contains(point)).collect();
+                    let eliminations = eliminations_of(table, &outside, value)?;
+                    if eliminations.is_empty() {
+                        continue;
+                    }
+                    return Result::Ok(Option::Some(locked_step(Technique::Pointing, &house, &line, places, value, eliminations)));
+                }
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+/// Finds a value whose places in a row or column all lie in one zone.
+/// The line needs the value there, so the rest of the zone loses it.
+pub struct Claiming {}
+
+impl StepFinder for Claiming {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        for (house, points) in SequenceValidator::houses(table)? {
+            if matches!(house, House::Zone(_)) {
+                continue;
+            }
+            for value in table.possible_values() {
+                let places = places_of(table, &points, value)?;
+                if places.len() < 2 {
+                    continue;
+                }
+                let zone_points = table.points_in_zone(&places[0])?;
+                if !places.iter().all(|point| zone_points.contains(point)) {
+                    continue;
+                }
+                let outside: Vec<Point> = zone_points.into_iter().filter(|point| !points.contains(point)).collect();
+                let eliminations = eliminations_of(table, &outside, value)?;
+                if eliminations.is_empty() {
+                    continue;
+                }
+                let zone = House::Zone(table.zone_index(&places[0])?);
+                return Result::Ok(Option::Some(locked_step(Technique::Claiming, &house, &zone, places, value, eliminations)));
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+fn places_of(table: &impl Candidates, points: &[Point], value: u8) -> Result<Vec<Point>, SudokuError> {
+    let mut places = Vec::new();
+    for point in points.iter() {
+        if table.candidates_in_point(point)? & mask_of(value) != 0 {
+            places.push(point.clone());
+        }
+    }
+    return Result::Ok(places);
+}
+
+fn eliminations_of(table: &impl Candidates, points: &[Point], value: u8) -> Result<Vec<(Point, u8)>, SudokuError> {
+    return Result::Ok(places_of(table, points, value)?.into_iter().map(|point| (point, value)).collect());
+}
+
+/// `value` is locked into `target` by its places in `source`.
+fn locked_step(technique: Technique, source: &House, target: &House, places: Vec<Point>, value: u8, eliminations: Vec<(Point, u8)>) -> Step {
+    let cells: Vec<String> = places.iter().map(cell_name).collect();
+    return Step {
+        technique: technique,
+        explanation: format!(
+            "In {}, {} can only go in {} ({}), so the rest of {} loses it: {}.",
+            house_name(source), value, house_name(target), list(&cells), house_name(target), describe_eliminations(&eliminations)
+        ),
+        cells: places,
+        candidates: vec![value],
+        placements: Vec::new(),
+        eliminations: eliminations,
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::{Table, Table6};
+
+    #[test]
+    fn test_finds_pointing() {
+        // 1 is taken out of the second and third rows of the first zone.
+        let mut t = Table::new();
+        for y in 1..3 {
+            for x in 0..3 {
+                t = *t.eliminate_in_point(&Point{x: x, y: y}, 1).unwrap();
+            }
+        }
+        assert!(Claiming{}.find(&t).unwrap().is_none());
+        let step = Pointing{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::Pointing);
+        assert_eq!(step.cells, vec![Point{x: 0, y: 0}, Point{x: 1, y: 0}, Point{x: 2, y: 0}]);
+        assert_eq!(step.eliminations, (3..9).map(|x| (Point{x: x, y: 0}, 1)).collect::<Vec<_>>());
+        assert_eq!(
+            step.explanation,
+            "In zone 1, 1 can only go in row 1 (r1c1, r1c2 and r1c3), so the rest of row 1 loses it: 1 from r1c4, r1c5, r1c6, r1c7, r1c8 and r1c9."
+        );
+    }
+
+    #[test]
+    fn test_finds_claiming() {
+        // 1 is taken out of the first column outside of the first zone.
+        let mut t = Table::new();
+        for y in 3..9 {
+            t = *t.eliminate_in_point(&Point{x: 0, y: y}, 1).unwrap();
+        }
+        assert!(Pointing{}.find(&t).unwrap().is_none());
+        let step = Claiming{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::Claiming);
+        assert_eq!(step.eliminations.len(), 6);
+        assert!(step.eliminations.iter().all(|(point, value)| *value == 1 && point.x > 0 && point.x < 3 && point.y < 3));
+        assert!(step.explanation.starts_with("In column 1, 1 can only go in zone 1"));
+    }
+
+    #[test]
+    fn test_claims_into_later_zone() {
+        // 1 only stays in the middle zone row of the fifth column.
+        let mut t = Table6::empty();
+        for y in [0, 1, 4, 5] {
+            t = *t.eliminate_in_point(&Point{x: 4, y: y}, 1).unwrap();
+        }
+        assert!(Pointing{}.find(&t).unwrap().is_none());
+        let step = Claiming{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.cells, vec![Point{x: 4, y: 2}, Point{x: 4, y: 3}]);
+        assert_eq!(
+            step.eliminations,
+            vec![(Point{x: 3, y: 2}, 1), (Point{x: 3, y: 3}, 1), (Point{x: 5, y: 2}, 1), (Point{x: 5, y: 3}, 1)]
+        );
+        assert!(step.explanation.starts_with("In column 5, 1 can only go in zone 4"));
+    }
+}
@@ -0,0 +1,319 @@
+pub mod singles;
+pub mod locked_candidates;
+pub mod subsets;
+pub mod fish;
+pub mod wings;
+
+use crate::{Candidates, Selectable, Settable, SudokuTable, SquareTable};
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::point_selection::line_point_selection::{LinePointSelection, SelectionType};
+use crate::validators::House;
+use singles::{HiddenSingles, NakedSingles};
+use locked_candidates::{Claiming, Pointing};
+use subsets::{HiddenSubsets, NakedSubsets};
+use fish::{FinnedFish, Fish};
+use wings::{WWing, XYWing, XYZWing};
+use std::fmt;
+
+/// A single deduction a person could make on the table.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Step {
+    pub technique: Technique,
+    /// Points the deduction is read from.
+    pub cells: Vec<Point>,
+    /// Values the deduction is about.
+    pub candidates: Vec<u8>,
+    pub placements: Vec<(Point, u8)>,
+    pub eliminations: Vec<(Point, u8)>,
+    pub explanation: String,
+}
+
+impl Step {
+    /// Places the values of the step, then removes its eliminated candidates.
+    pub fn apply<T>(&self, table: &T) -> Result<T, SudokuError> where T: Settable + Candidates + Clone {
+        let mut table = table.clone();
+        for (point, value) in self.placements.iter() {
+            table = *table.set_in_point(point, *value)?;
+        }
+        for (point, value) in self.eliminations.iter() {
+            table = *table.eliminate_in_point(point, *value)?;
+        }
+        return Result::Ok(table);
+    }
+}
+
+/// Human solving techniques, ordered from the easiest to the hardest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Technique {
+    /// A point with a single candidate left.
+    NakedSingle,
+    /// A value with a single possible point left in a row, column or zone.
+    HiddenSingle,
+    /// A value confined to one line within a zone, locked candidates of type 1.
+    Pointing,
+    /// A value confined to one zone within a line, locked candidates of type 2.
+    Claiming,
+    /// Two points of a house sharing the same two candidates.
+    NakedPair,
+    /// Two values of a house confined to the same two points.
+    HiddenPair,
+    NakedTriple,
+    HiddenTriple,
+    NakedQuad,
+    HiddenQuad,
+    /// Two rows holding a value only in the same two columns, or the other way round.
+    XWing,
+    Swordfish,
+    Jellyfish,
+    /// A pivot with two candidates seeing two pincers that share a third value.
+    XYWing,
+    /// A pivot with three candidates seeing two pincers made of them.
+    XYZWing,
+    /// Two points with the same two candidates joined by a strong link on one of them.
+    WWing,
+    /// An X-Wing with extra places, its fins, in one zone.
+    FinnedXWing,
+    /// A finned X-Wing that is only complete with its fins.
+    SashimiXWing,
+    FinnedSwordfish,
+    SashimiSwordfish,
+    FinnedJellyfish,
+    SashimiJellyfish,
+}
+
+impl Technique {
+    /// Every technique in the order they are tried.
+    pub const ALL: [Technique; 22] = [
+        Technique::NakedSingle,
+        Technique::HiddenSingle,
+        Technique::Pointing,
+        Technique::Claiming,
+        Technique::NakedPair,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::HiddenTriple,
+        Technique::NakedQuad,
+        Technique::HiddenQuad,
+        Technique::XWing,
+        Technique::Swordfish,
+        Technique::Jellyfish,
+        Technique::XYWing,
+        Technique::XYZWing,
+        Technique::WWing,
+        Technique::FinnedXWing,
+        Technique::SashimiXWing,
+        Technique::FinnedSwordfish,
+        Technique::SashimiSwordfish,
+        Technique::FinnedJellyfish,
+        Technique::SashimiJellyfish,
+    ];
+
+    /// Cheap techniques that only eliminate candidates, tried in this order by
+    /// [`ReductionFiller`](crate::fillers::reduction_filler::ReductionFiller)
+    /// on every branch of the search.
+    pub const ELIMINATIONS: [Technique; 8] = [
+        Technique::Pointing,
+        Technique::Claiming,
+        Technique::NakedPair,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::HiddenTriple,
+        Technique::NakedQuad,
+        Technique::HiddenQuad,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Technique::NakedSingle => "Naked single",
+            Technique::HiddenSingle => "Hidden single",
+            Technique::Pointing => "Pointing",
+            Technique::Claiming => "Claiming",
+            Technique::NakedPair => "Naked pair",
+            Technique::HiddenPair => "Hidden pair",
+            Technique::NakedTriple => "Naked triple",
+            Technique::HiddenTriple => "Hidden triple",
+            Technique::NakedQuad => "Naked quad",
+            Technique::HiddenQuad => "Hidden quad",
+            Technique::XWing => "X-Wing",
+            Technique::Swordfish => "Swordfish",
+            Technique::Jellyfish => "Jellyfish",
+            Technique::XYWing => "XY-Wing",
+            Technique::XYZWing => "XYZ-Wing",
+            Technique::WWing => "W-Wing",
+            Technique::FinnedXWing => "Finned X-Wing",
+            Technique::SashimiXWing => "Sashimi X-Wing",
+            Technique::FinnedSwordfish => "Finned Swordfish",
+            Technique::SashimiSwordfish => "Sashimi Swordfish",
+            Technique::FinnedJellyfish => "Finned Jellyfish",
+            Technique::SashimiJellyfish => "Sashimi Jellyfish",
+        };
+    }
+}
+
+impl fmt::Display for Technique {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.name());
+    }
+}
+
+/// Looks for the next deduction of one technique.
+pub trait StepFinder {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates;
+}
+
+impl StepFinder for Technique {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        match self {
+            Technique::NakedSingle => NakedSingles{}.find(table),
+            Technique::HiddenSingle => HiddenSingles{}.find(table),
+            Technique::Pointing => Pointing{}.find(table),
+            Technique::Claiming => Claiming{}.find(table),
+            Technique::NakedPair => NakedSubsets{ size: 2 }.find(table),
+            Technique::HiddenPair => HiddenSubsets{ size: 2 }.find(table),
+            Technique::NakedTriple => NakedSubsets{ size: 3 }.find(table),
+            Technique::HiddenTriple => HiddenSubsets{ size: 3 }.find(table),
+            Technique::NakedQuad => NakedSubsets{ size: 4 }.find(table),
+            Technique::HiddenQuad => HiddenSubsets{ size: 4 }.find(table),
+            Technique::XWing => Fish{ size: 2 }.find(table),
+            Technique::Swordfish => Fish{ size: 3 }.find(table),
+            Technique::Jellyfish => Fish{ size: 4 }.find(table),
+            Technique::XYWing => XYWing{}.find(table),
+            Technique::XYZWing => XYZWing{}.find(table),
+            Technique::WWing => WWing{}.find(table),
+            Technique::FinnedXWing => FinnedFish{ size: 2, sashimi: false }.find(table),
+            Technique::SashimiXWing => FinnedFish{ size: 2, sashimi: true }.find(table),
+            Technique::FinnedSwordfish => FinnedFish{ size: 3, sashimi: false }.find(table),
+            Technique::SashimiSwordfish => FinnedFish{ size: 3, sashimi: true }.find(table),
+            Technique::FinnedJellyfish => FinnedFish{ size: 4, sashimi: false }.find(table),
+            Technique::SashimiJellyfish => FinnedFish{ size: 4, sashimi: true }.find(table),
+        }
+    }
+}
+
+/// Names `point` the way players do, `r3c5` for the fifth point of the third row.
+pub fn cell_name(point: &Point) -> String {
+    return format!("r{}c{}", point.y + 1, point.x + 1);
+}
+
+pub fn house_name(house: &House) -> String {
+    let index = match house {
+        House::Row(y) => y,
+        House::Column(x) => x,
+        House::Zone(zone) => zone,
+    };
+    return format!("{} {}", house_kind(house), index + 1);
+}
+
+/// `row`, `column` or `zone`.
+pub fn house_kind(house: &House) -> &'static str {
+    return match house {
+        House::Row(_) => "row",
+        House::Column(_) => "column",
+        House::Zone(_) => "zone",
+    };
+}
+
+/// Joins names as `a`, `a and b` or `a, b and c`.
+pub fn list(names: &[String]) -> String {
+    return match names.split_last() {
+        None => String::new(),
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} and {}", rest.join(", "), last),
+    };
+}
+
+/// Describes eliminations grouped by value, `3 from r1c2 and r1c5; 7 from r4c4`.
+pub fn describe_eliminations(eliminations: &[(Point, u8)]) -> String {
+    let mut values: Vec<u8> = eliminations.iter().map(|(_, value)| *value).collect();
+    values.sort();
+    values.dedup();
+    let groups: Vec<String> = values.iter().map(|value| {
+        let cells: Vec<String> = eliminations.iter()
+            .filter(|(_, eliminated)| eliminated == value)
+            .map(|(point, _)| cell_name(point))
+            .collect();
+        return format!("{} from {}", value, list(&cells));
+    }).collect();
+    return groups.join("; ");
+}
+
+/// Every way of picking `size` of `items`, keeping their order.
+pub(crate) fn combinations<T: Clone>(items: &[T], size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut picked = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        for mut rest in combinations(&items[i + 1..], size - 1) {
+            rest.insert(0, item.clone());
+            picked.push(rest);
+        }
+    }
+    return picked;
+}
+
+/// Points sharing a row, column or zone with `point`, without `point` itself.
+pub(crate) fn peers_of(table: &(impl SudokuTable + SquareTable), point: &Point) -> Result<Vec<Point>, SudokuError> {
+    let line_point_selection = LinePointSelection{};
+    let mut houses = line_point_selection.get_points(table, point, SelectionType::Horizontal)?;
+    houses.extend(line_point_selection.get_points(table, point, SelectionType::Vertical)?);
+    houses.extend(table.points_in_zone(point)?);
+    let mut peers = Vec::with_capacity(houses.len());
+    for peer in houses {
+        if peer != *point && !peers.contains(&peer) {
+            peers.push(peer);
+        }
+    }
+    return Result::Ok(peers);
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::candidates::mask_of;
+    use crate::table::{Table, Table4};
+
+    /// Eliminates every value but `keep` from `point`, for hand-built
+    /// candidate tables in the technique tests.
+    pub(crate) fn with_candidates<const SIZE: usize, const ZONE_HEIGHT: usize, const ZONE_WIDTH: usize>(
+        table: Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH>,
+        point: &Point,
+        keep: &[u8],
+    ) -> Table<SIZE, ZONE_HEIGHT, ZONE_WIDTH> {
+        let mut table = table;
+        for value in table.possible_values() {
+            if !keep.contains(&value) && table.candidates_in_point(point).unwrap() & mask_of(value) != 0 {
+                table = *table.eliminate_in_point(point, value).unwrap();
+            }
+        }
+        return table;
+    }
+
+    #[test]
+    fn test_peers() {
+        let peers = peers_of(&Table::new(), &Point{x: 4, y: 4}).unwrap();
+        assert_eq!(peers.len(), 20);
+        assert!(peers.contains(&Point{x: 3, y: 5}));
+        assert!(!peers.contains(&Point{x: 4, y: 4}));
+        assert!(!peers.contains(&Point{x: 2, y: 2}));
+        assert_eq!(peers_of(&Table4::empty(), &Point{x: 0, y: 0}).unwrap().len(), 7);
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(&[1, 2, 3], 2), vec![vec![1, 2], vec![1, 3], vec![2, 3]]);
+        assert_eq!(combinations(&[1, 2, 3, 4, 5, 6, 7, 8, 9], 4).len(), 126);
+        assert!(combinations(&[1, 2], 3).is_empty());
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(cell_name(&Point{x: 4, y: 2}), "r3c5");
+        assert_eq!(house_name(&House::Zone(0)), "zone 1");
+        let eliminations = vec![(Point{x: 1, y: 0}, 3), (Point{x: 3, y: 3}, 7), (Point{x: 4, y: 0}, 3)];
+        assert_eq!(describe_eliminations(&eliminations), "3 from r1c2 and r1c5; 7 from r4c4");
+    }
+}
@@ -0,0 +1,97 @@
+use super::{cell_name, house_name, Step, StepFinder, Technique};
+use crate::{Candidates, Selectable, SudokuTable, SquareTable};
+use crate::candidates::mask_of;
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::validators::sequence_validator::SequenceValidator;
+
+/// Finds a point with a single candidate left.
+pub struct NakedSingles {}
+
+impl StepFinder for NakedSingles {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        for y in 0..table.dimensions() {
+            for x in 0..table.dimensions() {
+                let point = Point{x: x, y: y};
+                if table.value_in_point(&point)? != 0 {
+                    continue;
+                }
+                let candidates = table.candidates_in_point(&point)?;
+                if candidates.count_ones() != 1 {
+                    continue;
+                }
+                let value = candidates.trailing_zeros() as u8;
+                return Result::Ok(Option::Some(Step {
+                    technique: Technique::NakedSingle,
+                    explanation: format!("{} is the only candidate left in {}.", value, cell_name(&point)),
+                    cells: vec![point.clone()],
+                    candidates: vec![value],
+                    placements: vec![(point, value)],
+                    eliminations: Vec::new(),
+                }));
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+/// Finds a value with a single possible point left in a row, column or zone.
+pub struct HiddenSingles {}
+
+impl StepFinder for HiddenSingles {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        for (house, points) in SequenceValidator::houses(table)? {
+            for value in table.possible_values() {
+                let mut places = Vec::new();
+                for point in points.iter() {
+                    if table.candidates_in_point(point)? & mask_of(value) != 0 {
+                        places.push(point);
+                    }
+                }
+                if places.len() != 1 {
+                    continue;
+                }
+                let point = places[0].clone();
+                return Result::Ok(Option::Some(Step {
+                    technique: Technique::HiddenSingle,
+                    explanation: format!("{} can only go in {} within {}.", value, cell_name(&point), house_name(&house)),
+                    cells: vec![point.clone()],
+                    candidates: vec![value],
+                    placements: vec![(point, value)],
+                    eliminations: Vec::new(),
+                }));
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table;
+
+    #[test]
+    fn test_finds_naked_single() {
+        let t: Table = "12345678.........................................................................".parse().unwrap();
+        let step = NakedSingles{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::NakedSingle);
+        assert_eq!(step.placements, vec![(Point{x: 8, y: 0}, 9)]);
+        assert_eq!(step.explanation, "9 is the only candidate left in r1c9.");
+    }
+
+    #[test]
+    fn test_finds_hidden_single() {
+        // 1 is blocked from every point of the first zone except r1c1.
+        let mut fields = [[0u8; 9]; 9];
+        fields[1][4] = 1;
+        fields[2][7] = 1;
+        fields[4][1] = 1;
+        fields[7][2] = 1;
+        let t = Table::new_from(fields);
+        assert!(NakedSingles{}.find(&t).unwrap().is_none());
+        let step = HiddenSingles{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.placements, vec![(Point{x: 0, y: 0}, 1)]);
+        assert_eq!(step.explanation, "1 can only go in r1c1 within row 1.");
+    }
+}
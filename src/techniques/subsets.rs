@@ -0,0 +1,216 @@
+use super::{cell_name, combinations, describe_eliminations, house_kind, house_name, list, Step, StepFinder, Technique};
+use crate::{Candidates, Selectable, SudokuTable, SquareTable};
+use crate::candidates::{mask_of, mask_values, CandidateMask};
+use crate::error::SudokuError;
+use crate::validators::sequence_validator::SequenceValidator;
+
+/// Finds `size` points of a house holding only `size` candidates between
+/// them. Those values must go in these points, so they are removed from
+/// the rest of the house.
+pub struct NakedSubsets {
+    pub size: usize,
+}
+
+impl NakedSubsets {
+    fn technique(&self) -> Technique {
+        return match self.size {
+            2 => Technique::NakedPair,
+            3 => Technique::NakedTriple,
+            _ => Technique::NakedQuad,
+        };
+    }
+}
+
+impl StepFinder for NakedSubsets {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        for (house, points) in SequenceValidator::houses(table)? {
+            let mut members = Vec::new();
+            for point in points.iter() {
+                let count = table.candidates_in_point(point)?.count_ones() as usize;
+                if count >= 2 && count <= self.size {
+                    members.push(point.clone());
+                }
+            }
+            for subset in combinations(&members, self.size) {
+                let mut values: CandidateMask = 0;
+                for point in subset.iter() {
+                    values |= table.candidates_in_point(point)?;
+                }
+                if values.count_ones() as usize != self.size {
+                    continue;
+                }
+                let mut eliminations = Vec::new();
+                for point in points.iter().filter(|point| !subset.contains(point)) {
+                    for value in mask_values(table.candidates_in_point(point)? & values) {
+                        eliminations.push((point.clone(), value));
+                    }
+                }
+                if eliminations.is_empty() {
+                    continue;
+                }
+                let values = mask_values(values);
+                let cells: Vec<String> = subset.iter().map(cell_name).collect();
+                let names: Vec<String> = values.iter().map(u8::to_string).collect();
+                return Result::Ok(Option::Some(Step {
+                    technique: self.technique(),
+                    explanation: format!(
+                        "{} only hold {} in {}, so the rest of the {} loses them: {}.",
+                        list(&cells), list(&names), house_name(&house), house_kind(&house), describe_eliminations(&eliminations)
+                    ),
+                    cells: subset,
+                    candidates: values,
+                    placements: Vec::new(),
+                    eliminations: eliminations,
+                }));
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+/// Finds `size` values that can only go in the same `size` points of a
+/// house. Those points must hold these values, so their other candidates
+/// are removed.
+pub struct HiddenSubsets {
+    pub size: usize,
+}
+
+impl HiddenSubsets {
+    fn technique(&self) -> Technique {
+        return match self.size {
+            2 => Technique::HiddenPair,
+            3 => Technique::HiddenTriple,
+            _ => Technique::HiddenQuad,
+        };
+    }
+}
+
+impl StepFinder for HiddenSubsets {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        for (house, points) in SequenceValidator::houses(table)? {
+            let mut members = Vec::new();
+            for value in table.possible_values() {
+                let mut places = 0;
+                for point in points.iter() {
+                    if table.candidates_in_point(point)? & mask_of(value) != 0 {
+                        places += 1;
+                    }
+                }
+                if places >= 2 && places <= self.size {
+                    members.push(value);
+                }
+            }
+            for values in combinations(&members, self.size) {
+                let mask = values.iter().fold(0, |mask, value| mask | mask_of(*value));
+                let mut cells = Vec::new();
+                for point in points.iter() {
+                    if table.candidates_in_point(point)? & mask != 0 {
+                        cells.push(point.clone());
+                    }
+                }
+                if cells.len() != self.size {
+                    continue;
+                }
+                let mut eliminations = Vec::new();
+                for point in cells.iter() {
+                    for value in mask_values(table.candidates_in_point(point)? & !mask) {
+                        eliminations.push((point.clone(), value));
+                    }
+                }
+                if eliminations.is_empty() {
+                    continue;
+                }
+                let names: Vec<String> = values.iter().map(u8::to_string).collect();
+                let cell_names: Vec<String> = cells.iter().map(cell_name).collect();
+                return Result::Ok(Option::Some(Step {
+                    technique: self.technique(),
+                    explanation: format!(
+                        "{} can only go in {} within {}, so those points lose their other candidates: {}.",
+                        list(&names), list(&cell_names), house_name(&house), describe_eliminations(&eliminations)
+                    ),
+                    cells: cells,
+                    candidates: values,
+                    placements: Vec::new(),
+                    eliminations: eliminations,
+                }));
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::point::Point;
+    use crate::table::{Table, Table4};
+    use crate::techniques::test::with_candidates;
+
+    #[test]
+    fn test_finds_naked_pair() {
+        let mut t = Table::new();
+        t = with_candidates(t, &Point{x: 0, y: 0}, &[1, 2]);
+        t = with_candidates(t, &Point{x: 4, y: 0}, &[1, 2]);
+        let step = NakedSubsets{ size: 2 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::NakedPair);
+        assert_eq!(step.cells, vec![Point{x: 0, y: 0}, Point{x: 4, y: 0}]);
+        assert_eq!(step.candidates, vec![1, 2]);
+        assert_eq!(step.eliminations.len(), 14);
+        assert!(step.eliminations.iter().all(|(point, value)| point.y == 0 && *value <= 2));
+        assert!(step.explanation.starts_with("r1c1 and r1c5 only hold 1 and 2 in row 1"));
+
+        let applied = step.apply(&t).unwrap();
+        assert!(NakedSubsets{ size: 2 }.find(&applied).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_finds_naked_pair_on_small_table() {
+        let mut t = Table4::empty();
+        t = with_candidates(t, &Point{x: 0, y: 0}, &[1, 2]);
+        t = with_candidates(t, &Point{x: 2, y: 0}, &[1, 2]);
+        let step = NakedSubsets{ size: 2 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.cells, vec![Point{x: 0, y: 0}, Point{x: 2, y: 0}]);
+        assert_eq!(
+            step.eliminations,
+            vec![(Point{x: 1, y: 0}, 1), (Point{x: 1, y: 0}, 2), (Point{x: 3, y: 0}, 1), (Point{x: 3, y: 0}, 2)]
+        );
+        assert!(step.explanation.starts_with("r1c1 and r1c3 only hold 1 and 2 in row 1"));
+    }
+
+    #[test]
+    fn test_finds_naked_triple_and_quad() {
+        let mut t = Table::new();
+        t = with_candidates(t, &Point{x: 3, y: 0}, &[4, 5]);
+        t = with_candidates(t, &Point{x: 3, y: 4}, &[5, 6]);
+        t = with_candidates(t, &Point{x: 3, y: 8}, &[4, 6]);
+        assert!(NakedSubsets{ size: 2 }.find(&t).unwrap().is_none());
+        let step = NakedSubsets{ size: 3 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::NakedTriple);
+        assert_eq!(step.candidates, vec![4, 5, 6]);
+        assert!(step.eliminations.iter().all(|(point, _)| point.x == 3));
+
+        let mut t = Table::new();
+        for (x, keep) in [(0, [1, 2]), (1, [2, 3]), (2, [3, 4]), (3, [1, 4])].iter() {
+            t = with_candidates(t, &Point{x: *x, y: 6}, keep);
+        }
+        assert!(NakedSubsets{ size: 3 }.find(&t).unwrap().is_none());
+        assert_eq!(NakedSubsets{ size: 4 }.find(&t).unwrap().unwrap().technique, Technique::NakedQuad);
+    }
+
+    #[test]
+    fn test_finds_hidden_pair() {
+        // 1 and 2 are taken out of every point of row 1 except r1c1 and r1c2.
+        let mut t = Table::new();
+        for x in 2..9 {
+            t = *t.eliminate_in_point(&Point{x: x, y: 0}, 1).unwrap();
+            t = *t.eliminate_in_point(&Point{x: x, y: 0}, 2).unwrap();
+        }
+        assert!(NakedSubsets{ size: 2 }.find(&t).unwrap().is_none());
+        let step = HiddenSubsets{ size: 2 }.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::HiddenPair);
+        assert_eq!(step.cells, vec![Point{x: 0, y: 0}, Point{x: 1, y: 0}]);
+        assert_eq!(step.eliminations.len(), 14);
+        let applied = step.apply(&t).unwrap();
+        assert_eq!(applied.candidates_in_point(&Point{x: 1, y: 0}).unwrap(), mask_of(1) | mask_of(2));
+    }
+}
@@ -0,0 +1,246 @@
+use super::{cell_name, combinations, describe_eliminations, house_name, peers_of, Step, StepFinder, Technique};
+use crate::{Candidates, Selectable, SudokuTable, SquareTable};
+use crate::candidates::{mask_of, mask_values, CandidateMask};
+use crate::error::SudokuError;
+use crate::point::Point;
+use crate::validators::sequence_validator::SequenceValidator;
+
+/// Empty points with their candidates.
+fn cells_with_candidates(table: &(impl Candidates + SquareTable)) -> Result<Vec<(Point, CandidateMask)>, SudokuError> {
+    let mut cells = Vec::new();
+    for y in 0..table.dimensions() {
+        for x in 0..table.dimensions() {
+            let point = Point{x: x, y: y};
+            let candidates = table.candidates_in_point(&point)?;
+            if candidates != 0 {
+                cells.push((point, candidates));
+            }
+        }
+    }
+    return Result::Ok(cells);
+}
+
+/// Points holding `value` as a candidate that see every point of `seen`.
+fn eliminations_seeing(
+    table: &(impl SudokuTable + SquareTable),
+    cells: &[(Point, CandidateMask)],
+    seen: &[&Point],
+    value: u8,
+) -> Result<Vec<(Point, u8)>, SudokuError> {
+    let mut seen_peers = Vec::with_capacity(seen.len());
+    for point in seen.iter() {
+        seen_peers.push(peers_of(table, point)?);
+    }
+    return Result::Ok(cells.iter()
+        .filter(|(point, candidates)| candidates & mask_of(value) != 0 && seen_peers.iter().all(|peers| peers.contains(point)))
+        .map(|(point, _)| (point.clone(), value))
+        .collect());
+}
+
+/// `r2c3 (4/7)`.
+fn cell_with_candidates(point: &Point, candidates: CandidateMask) -> String {
+    let values: Vec<String> = mask_values(candidates).iter().map(u8::to_string).collect();
+    return format!("{} ({})", cell_name(point), values.join("/"));
+}
+
+/// Finds a pivot with two candidates `xy` seeing two pincers `xz` and
+/// `yz`. Whatever the pivot holds, one pincer holds `z`, so points seeing
+/// both pincers lose `z`.
+pub struct XYWing {}
+
+impl StepFinder for XYWing {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        let cells = cells_with_candidates(table)?;
+        let bivalue: Vec<&(Point, CandidateMask)> = cells.iter().filter(|(_, candidates)| candidates.count_ones() == 2).collect();
+        for (pivot, pivot_candidates) in bivalue.iter().cloned() {
+            let pivot_peers = peers_of(table, pivot)?;
+            let pincers: Vec<&(Point, CandidateMask)> = bivalue.iter()
+                .filter(|(point, candidates)| pivot_peers.contains(point) && (candidates & pivot_candidates).count_ones() == 1)
+                .cloned()
+                .collect();
+            for pair in combinations(&pincers, 2) {
+                let ((first, first_candidates), (second, second_candidates)) = (pair[0], pair[1]);
+                let shared = first_candidates & second_candidates;
+                if shared.count_ones() != 1 || shared & pivot_candidates != 0
+                    || (first_candidates | second_candidates) & pivot_candidates != *pivot_candidates {
+                    continue;
+                }
+                let value = shared.trailing_zeros() as u8;
+                let eliminations = eliminations_seeing(table, &cells, &[first, second], value)?;
+                if eliminations.is_empty() {
+                    continue;
+                }
+                return Result::Ok(Option::Some(Step {
+                    technique: Technique::XYWing,
+                    explanation: format!(
+                        "XY-Wing with pivot {} and pincers {} and {}: whatever the pivot holds, one pincer holds {}, so points seeing both pincers lose it: {}.",
+                        cell_with_candidates(pivot, *pivot_candidates), cell_with_candidates(first, *first_candidates),
+                        cell_with_candidates(second, *second_candidates), value, describe_eliminations(&eliminations)
+                    ),
+                    cells: vec![pivot.clone(), first.clone(), second.clone()],
+                    candidates: mask_values(*pivot_candidates | shared),
+                    placements: Vec::new(),
+                    eliminations: eliminations,
+                }));
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+/// Finds a pivot with three candidates `xyz` seeing two pincers `xz` and
+/// `yz`. One of the three holds `z`, so points seeing all of them lose it.
+pub struct XYZWing {}
+
+impl StepFinder for XYZWing {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        let cells = cells_with_candidates(table)?;
+        for (pivot, pivot_candidates) in cells.iter().filter(|(_, candidates)| candidates.count_ones() == 3) {
+            let pivot_peers = peers_of(table, pivot)?;
+            let pincers: Vec<&(Point, CandidateMask)> = cells.iter()
+                .filter(|(point, candidates)| {
+                    candidates.count_ones() == 2 && candidates & !pivot_candidates == 0 && pivot_peers.contains(point)
+                })
+                .collect();
+            for pair in combinations(&pincers, 2) {
+                let ((first, first_candidates), (second, second_candidates)) = (pair[0], pair[1]);
+                let shared = first_candidates & second_candidates;
+                if shared.count_ones() != 1 {
+                    continue;
+                }
+                let value = shared.trailing_zeros() as u8;
+                let eliminations = eliminations_seeing(table, &cells, &[pivot, first, second], value)?;
+                if eliminations.is_empty() {
+                    continue;
+                }
+                return Result::Ok(Option::Some(Step {
+                    technique: Technique::XYZWing,
+                    explanation: format!(
+                        "XYZ-Wing with pivot {} and pincers {} and {}: one of them holds {}, so points seeing all three lose it: {}.",
+                        cell_with_candidates(pivot, *pivot_candidates), cell_with_candidates(first, *first_candidates),
+                        cell_with_candidates(second, *second_candidates), value, describe_eliminations(&eliminations)
+                    ),
+                    cells: vec![pivot.clone(), first.clone(), second.clone()],
+                    candidates: mask_values(*pivot_candidates),
+                    placements: Vec::new(),
+                    eliminations: eliminations,
+                }));
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+/// Finds two points with the same two candidates `xy` that do not see each
+/// other, joined by a house where `x` has exactly two places, one seeing
+/// each point. One of the two points holds `x`, so the other holds `y`
+/// and points seeing both lose `y`.
+pub struct WWing {}
+
+impl StepFinder for WWing {
+    fn find<T>(&self, table: &T) -> Result<Option<Step>, SudokuError> where T: Selectable + SudokuTable + SquareTable + Candidates {
+        let cells = cells_with_candidates(table)?;
+        let houses = SequenceValidator::houses(table)?;
+        let bivalue: Vec<&(Point, CandidateMask)> = cells.iter().filter(|(_, candidates)| candidates.count_ones() == 2).collect();
+        for pair in combinations(&bivalue, 2) {
+            let ((first, candidates), (second, second_candidates)) = (pair[0], pair[1]);
+            if candidates != second_candidates {
+                continue;
+            }
+            let first_peers = peers_of(table, first)?;
+            if first_peers.contains(second) {
+                continue;
+            }
+            let second_peers = peers_of(table, second)?;
+            let values = mask_values(*candidates);
+            for (link_value, value) in [(values[0], values[1]), (values[1], values[0])] {
+                let eliminations = eliminations_seeing(table, &cells, &[first, second], value)?;
+                if eliminations.is_empty() {
+                    continue;
+                }
+                for (house, points) in houses.iter() {
+                    let mut places = Vec::new();
+                    for point in points.iter() {
+                        if table.candidates_in_point(point)? & mask_of(link_value) != 0 {
+                            places.push(point);
+                        }
+                    }
+                    if places.len() != 2 || places.contains(&first) || places.contains(&second) {
+                        continue;
+                    }
+                    let (near, far) = if first_peers.contains(places[0]) && second_peers.contains(places[1]) {
+                        (places[0], places[1])
+                    } else if first_peers.contains(places[1]) && second_peers.contains(places[0]) {
+                        (places[1], places[0])
+                    } else {
+                        continue;
+                    };
+                    return Result::Ok(Option::Some(Step {
+                        technique: Technique::WWing,
+                        explanation: format!(
+                            "W-Wing on {} and {} linked by {} in {} ({} and {}): one of them holds {}, so the other holds {} and points seeing both lose it: {}.",
+                            cell_with_candidates(first, *candidates), cell_with_candidates(second, *candidates), link_value,
+                            house_name(house), cell_name(near), cell_name(far), link_value, value, describe_eliminations(&eliminations)
+                        ),
+                        cells: vec![first.clone(), second.clone(), near.clone(), far.clone()],
+                        candidates: mask_values(*candidates),
+                        placements: Vec::new(),
+                        eliminations: eliminations,
+                    }));
+                }
+            }
+        }
+        return Result::Ok(Option::None);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::table::Table;
+    use crate::techniques::test::with_candidates;
+
+
+    #[test]
+    fn test_finds_xy_wing() {
+        let mut t = Table::new();
+        t = with_candidates(t, &Point{x: 0, y: 0}, &[1, 2]);
+        t = with_candidates(t, &Point{x: 4, y: 0}, &[1, 3]);
+        t = with_candidates(t, &Point{x: 0, y: 4}, &[2, 3]);
+        let step = XYWing{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::XYWing);
+        assert_eq!(step.cells, vec![Point{x: 0, y: 0}, Point{x: 4, y: 0}, Point{x: 0, y: 4}]);
+        assert_eq!(step.eliminations, vec![(Point{x: 4, y: 4}, 3)]);
+        assert_eq!(
+            step.explanation,
+            "XY-Wing with pivot r1c1 (1/2) and pincers r1c5 (1/3) and r5c1 (2/3): whatever the pivot holds, one pincer holds 3, so points seeing both pincers lose it: 3 from r5c5."
+        );
+    }
+
+    #[test]
+    fn test_finds_xyz_wing() {
+        let mut t = Table::new();
+        t = with_candidates(t, &Point{x: 0, y: 0}, &[1, 2, 3]);
+        t = with_candidates(t, &Point{x: 4, y: 0}, &[1, 3]);
+        t = with_candidates(t, &Point{x: 1, y: 1}, &[2, 3]);
+        assert!(XYWing{}.find(&t).unwrap().is_none());
+        let step = XYZWing{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::XYZWing);
+        assert_eq!(step.eliminations, vec![(Point{x: 1, y: 0}, 3), (Point{x: 2, y: 0}, 3)]);
+    }
+
+    #[test]
+    fn test_finds_w_wing() {
+        let mut t = Table::new();
+        t = with_candidates(t, &Point{x: 0, y: 0}, &[1, 2]);
+        t = with_candidates(t, &Point{x: 8, y: 4}, &[1, 2]);
+        for x in 1..8 {
+            t = *t.eliminate_in_point(&Point{x: x, y: 8}, 1).unwrap();
+        }
+        let step = WWing{}.find(&t).unwrap().unwrap();
+        assert_eq!(step.technique, Technique::WWing);
+        assert_eq!(step.cells, vec![Point{x: 0, y: 0}, Point{x: 8, y: 4}, Point{x: 0, y: 8}, Point{x: 8, y: 8}]);
+        assert_eq!(step.eliminations, vec![(Point{x: 8, y: 0}, 2), (Point{x: 0, y: 4}, 2)]);
+        assert!(step.explanation.starts_with("W-Wing on r1c1 (1/2) and r5c9 (1/2) linked by 1 in row 9 (r9c1 and r9c9)"));
+    }
+}
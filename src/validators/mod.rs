@@ -1,10 +1,59 @@
-use crate::Selectable;
+use crate::{Selectable, SquareTable};
 use crate::SudokuTable;
 use crate::point::Point;
+use crate::error::SudokuError;
 pub mod sequence_validator;
 
+/// A row, column or zone of the table, identified by its index.
+///
+/// Zones are numbered left to right, top to bottom.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum House {
+    Row(usize),
+    Column(usize),
+    Zone(usize),
+}
+
+/// Two points of the same house holding the same value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub first: Point,
+    pub second: Point,
+    pub value: u8,
+    pub house: House,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    pub conflicts: Vec<Conflict>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        return self.conflicts.is_empty();
+    }
+}
 
 pub trait TableValidator {
-    fn get_possibilites(&self, table: &(impl Selectable + SudokuTable), point: Vec<&Point>) -> Vec<u8>;
-    fn validate(&self, table: impl Selectable + SudokuTable, point: Vec<&Point>) -> bool;
+    fn get_possibilites(&self, table: &(impl Selectable + SudokuTable), point: Vec<&Point>) -> Result<Vec<u8>, SudokuError>;
+    fn validate(&self, table: &(impl Selectable + SudokuTable + SquareTable)) -> Result<ValidationReport, SudokuError>;
+
+    /// Fails on the first value outside of the table's possible values, then on any rule conflict.
+    fn ensure_valid(&self, table: &(impl Selectable + SudokuTable + SquareTable)) -> Result<(), SudokuError> {
+        let size = table.dimensions();
+        for y in 0..size {
+            for x in 0..size {
+                let point = Point{x: x, y: y};
+                let value = table.value_in_point(&point)?;
+                if value as usize > size {
+                    return Result::Err(SudokuError::InvalidDigit { point: point, value: value });
+                }
+            }
+        }
+        let report = self.validate(table)?;
+        if !report.is_valid() {
+            return Result::Err(SudokuError::Conflict(report.conflicts));
+        }
+        return Result::Ok(());
+    }
 }
@@ -1,27 +1,95 @@
-use crate::Selectable;
+use crate::{Selectable, SquareTable};
 use crate::SudokuTable;
-use super::TableValidator;
+use super::{Conflict, House, TableValidator, ValidationReport};
 use crate::point::Point;
+use crate::error::SudokuError;
+use crate::point_selection::line_point_selection::{LinePointSelection, SelectionType};
 
 pub struct SequenceValidator {
     
 }
 
 impl TableValidator for SequenceValidator {
-    fn get_possibilites(&self, table: &(impl Selectable + SudokuTable), point: Vec<&Point>) -> Vec<u8> {
+    fn get_possibilites(&self, table: &(impl Selectable + SudokuTable), point: Vec<&Point>) -> Result<Vec<u8>, SudokuError> {
         let possibilites = table.possible_values();
-        let current_values: Vec<u8> = point.into_iter().filter_map(|p| -> Option<u8> {
-            let value_in_point = table.value_in_point(p).unwrap();
-            if value_in_point == 0 {
-                return Option::None;
+        let mut current_values: Vec<u8> = Vec::with_capacity(point.len());
+        for p in point {
+            let value_in_point = table.value_in_point(p)?;
+            if value_in_point != 0 {
+                current_values.push(value_in_point);
             }
-            return Option::Some(value_in_point);
-        }).collect();
-        return possibilites.into_iter().filter(|v| !current_values.iter().any(|c| c == v)).collect();
+        }
+        return Result::Ok(possibilites.into_iter().filter(|v| !current_values.iter().any(|c| c == v)).collect());
     }
 
-    fn validate(&self, _table: impl Selectable + SudokuTable, _point: Vec<&Point>) -> bool {
-        return true;
+    fn validate(&self, table: &(impl Selectable + SudokuTable + SquareTable)) -> Result<ValidationReport, SudokuError> {
+        let mut report = ValidationReport::default();
+        for (house, points) in SequenceValidator::houses(table)? {
+            SequenceValidator::collect_conflicts(table, house, &points, &mut report.conflicts)?;
+        }
+        return Result::Ok(report);
+    }
+}
+
+impl SequenceValidator {
+    /// Values that can still be placed in `point` without repeating one in its row, column or zone.
+    pub fn get_point_possibilites(&self, table: &(impl Selectable + SudokuTable + SquareTable), point: &Point) -> Result<Vec<u8>, SudokuError> {
+        let line_point_selection = LinePointSelection{};
+        let zone_points = table.points_in_zone(point)?;
+        let zone_possibilites = self.get_possibilites(table, zone_points.iter().collect())?;
+        let vertical_points = line_point_selection.get_points(table, point, SelectionType::Vertical)?;
+        let horizontal_points = line_point_selection.get_points(table, point, SelectionType::Horizontal)?;
+        let vertical_possibilites = self.get_possibilites(table, vertical_points.iter().collect())?;
+        let horizontal_possibilites = self.get_possibilites(table, horizontal_points.iter().collect())?;
+        return Result::Ok(zone_possibilites.into_iter()
+            .filter(|v| vertical_possibilites.contains(v))
+            .filter(|v| horizontal_possibilites.contains(v))
+            .collect());
+    }
+
+    /// Every row, column and zone of the table together with its points.
+    pub fn houses(table: &(impl SudokuTable + SquareTable)) -> Result<Vec<(House, Vec<Point>)>, SudokuError> {
+        let size = table.dimensions();
+        let mut houses = Vec::with_capacity(size * 3);
+        for y in 0..size {
+            houses.push((House::Row(y), (0..size).map(|x| Point{x: x, y: y}).collect()));
+        }
+        for x in 0..size {
+            houses.push((House::Column(x), (0..size).map(|y| Point{x: x, y: y}).collect()));
+        }
+        let mut zone_count = 0;
+        for y in 0..size {
+            for x in 0..size {
+                let point = Point{x: x, y: y};
+                let zone = table.points_in_zone(&point)?;
+                let first = zone.iter().min_by_key(|p| (p.y, p.x));
+                if first == Some(&point) {
+                    houses.push((House::Zone(zone_count), zone));
+                    zone_count += 1;
+                }
+            }
+        }
+        return Result::Ok(houses);
+    }
+
+    fn collect_conflicts(table: &impl Selectable, house: House, points: &[Point], conflicts: &mut Vec<Conflict>) -> Result<(), SudokuError> {
+        for (i, first) in points.iter().enumerate() {
+            let value = table.value_in_point(first)?;
+            if value == 0 {
+                continue;
+            }
+            for second in points[i + 1..].iter() {
+                if table.value_in_point(second)? == value {
+                    conflicts.push(Conflict{
+                        first: first.clone(),
+                        second: second.clone(),
+                        value: value,
+                        house: house,
+                    });
+                }
+            }
+        }
+        return Result::Ok(());
     }
 }
 
@@ -32,6 +100,8 @@ mod test {
     use super::TableValidator;
     use crate::Settable;
     use crate::point::Point;
+    use crate::validators::House;
+    use crate::error::SudokuError;
     #[test]
     fn test_should_return_valid_possibilites() {
         let mut t = Table::new();
@@ -39,11 +109,8 @@ mod test {
         t = *t.set_in_point(&Point{x: 1, y: 3}, 6).unwrap();
 
         let s = SequenceValidator{};
-        let mut point_vec = Vec::new();
-        point_vec.push(&Point{x: 1, y: 2});
-        point_vec.push(&Point{x: 1, y: 3});
-        point_vec.push(&Point{x: 1, y: 6});
-        let possible_vales = s.get_possibilites(&t, point_vec);
+        let point_vec = vec![&Point{x: 1, y: 2}, &Point{x: 1, y: 3}, &Point{x: 1, y: 6}];
+        let possible_vales = s.get_possibilites(&t, point_vec).unwrap();
         assert_eq!(possible_vales.len(), 7);
         assert_eq!(possible_vales[0], 1);
         assert_eq!(possible_vales[1], 2);
@@ -53,4 +120,66 @@ mod test {
         assert_eq!(possible_vales[5], 8);
         assert_eq!(possible_vales[6], 9);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_validate_accepts_consistent_table() {
+        let mut t = Table::new();
+        t = *t.set_in_point(&Point{x: 0, y: 0}, 1).unwrap();
+        t = *t.set_in_point(&Point{x: 4, y: 4}, 1).unwrap();
+        t = *t.set_in_point(&Point{x: 8, y: 8}, 1).unwrap();
+
+        let report = SequenceValidator{}.validate(&t).unwrap();
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn test_validate_reports_every_house() {
+        let mut t = Table::new();
+        t = *t.set_in_point(&Point{x: 0, y: 0}, 4).unwrap();
+        t = *t.set_in_point(&Point{x: 1, y: 1}, 4).unwrap();
+        t = *t.set_in_point(&Point{x: 6, y: 0}, 4).unwrap();
+        t = *t.set_in_point(&Point{x: 0, y: 7}, 4).unwrap();
+
+        let report = SequenceValidator{}.validate(&t).unwrap();
+        assert_eq!(report.conflicts.len(), 3);
+        let houses: Vec<House> = report.conflicts.iter().map(|c| c.house).collect();
+        assert!(houses.contains(&House::Row(0)));
+        assert!(houses.contains(&House::Column(0)));
+        assert!(houses.contains(&House::Zone(0)));
+        let zone_conflict = report.conflicts.iter().find(|c| c.house == House::Zone(0)).unwrap();
+        assert_eq!(zone_conflict.first, Point{x: 0, y: 0});
+        assert_eq!(zone_conflict.second, Point{x: 1, y: 1});
+        assert_eq!(zone_conflict.value, 4);
+    }
+
+    #[test]
+    fn test_validate_rejects_filled_table_with_duplicates() {
+        let filled = Table::new_from([[1u8; 9]; 9]);
+        assert!(filled.is_filled());
+        assert!(!SequenceValidator{}.validate(&filled).unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_ensure_valid() {
+        let s = SequenceValidator{};
+        let mut t = Table::new();
+        t = *t.set_in_point(&Point{x: 2, y: 2}, 7).unwrap();
+        assert_eq!(s.ensure_valid(&t), Result::Ok(()));
+
+        t = *t.set_in_point(&Point{x: 2, y: 5}, 7).unwrap();
+        match s.ensure_valid(&t) {
+            Result::Err(SudokuError::Conflict(conflicts)) => assert_eq!(conflicts[0].house, House::Column(2)),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let mut fields = [[0u8; 9]; 9];
+        fields[3][1] = 10;
+        let error = s.ensure_valid(&Table::new_from(fields)).unwrap_err();
+        assert_eq!(error, SudokuError::InvalidDigit { point: Point{x: 1, y: 3}, value: 10 });
+
+        fields[3][1] = 0;
+        fields[6][4] = 42;
+        let error = s.ensure_valid(&Table::new_from(fields)).unwrap_err();
+        assert_eq!(error, SudokuError::InvalidDigit { point: Point{x: 4, y: 6}, value: 42 });
+    }
+}